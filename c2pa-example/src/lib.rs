@@ -13,14 +13,83 @@
 //!
 //! **When in doubt, check the macro documentation first!**
 
+use std::cell::RefCell;
+use std::ffi::c_void;
 use std::os::raw::c_char;
+use std::sync::{Mutex, OnceLock};
 
 use cimpl::{
     box_tracked, cimpl_free, cstr_or_return, cstr_or_return_null,
-    deref_or_return_neg, deref_or_return_null, deref_mut_or_return_neg,
-    ok_or_return, ok_or_return_null, option_to_c_string, to_c_string, CimplError,
+    deref_or_return_neg, deref_or_return_null, deref_or_return_zero, deref_mut_or_return_neg,
+    ok_or_return, ok_or_return_null, ok_or_return_out_err, option_to_c_string, to_c_string,
+    CimplError, ExternError,
 };
 
+// ============================================================================
+// Diagnostic / Log Callback
+// ============================================================================
+
+/// Severity of a message delivered to the host log callback.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+}
+
+/// Host-supplied diagnostic callback: `level` is a [`LogLevel`], `message` is
+/// a transient, NUL-terminated, borrowed string (valid only for the duration
+/// of the call - copy it if you need to keep it), and `user_data` is whatever
+/// was passed to [`c2pa_set_log_callback`].
+pub type C2paLogCallback =
+    extern "C" fn(level: i32, message: *const c_char, user_data: *mut c_void);
+
+struct LogSink {
+    callback: C2paLogCallback,
+    user_data: *mut c_void,
+}
+
+// Safety: `user_data` is an opaque host-owned pointer we never dereference
+// ourselves - it's only ever handed back to `callback`, which runs on
+// whatever thread emits the log line. The host is responsible for it being
+// safe to access from there.
+unsafe impl Send for LogSink {}
+
+fn log_sink() -> &'static Mutex<Option<LogSink>> {
+    static SINK: OnceLock<Mutex<Option<LogSink>>> = OnceLock::new();
+    SINK.get_or_init(|| Mutex::new(None))
+}
+
+/// Registers a host callback to receive diagnostics as they happen, instead
+/// of the host having to poll the last-error slot after a failure: a `Warn`
+/// message for every C2PA error reported through the last-error slot
+/// (including settings parse problems), and an `Info` message whenever
+/// context settings or verify flags are applied.
+///
+/// Pass `None` for `cb` to clear a previously registered callback - no
+/// callback means every emitted diagnostic is silently dropped. Returns 0 on
+/// success (this never fails; the return value exists for future use and
+/// parity with the rest of the C2PA API).
+#[no_mangle]
+pub extern "C" fn c2pa_set_log_callback(cb: Option<C2paLogCallback>, user_data: *mut c_void) -> i32 {
+    let mut sink = log_sink().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *sink = cb.map(|callback| LogSink { callback, user_data });
+    0
+}
+
+/// Emits a diagnostic to the registered host callback, if any. A no-op when
+/// no callback is registered, or when `message` contains an interior NUL.
+fn emit_log(level: LogLevel, message: &str) {
+    let sink = log_sink().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(sink) = sink.as_ref() {
+        if let Ok(c_message) = std::ffi::CString::new(message) {
+            (sink.callback)(level as i32, c_message.as_ptr(), sink.user_data);
+        }
+    }
+}
+
 // ============================================================================
 // Error Handling
 // ============================================================================
@@ -58,26 +127,92 @@ impl From<serde_json::Error> for C2paInternalError {
     }
 }
 
+// Thread-local cause chain for the error currently held in the CimplError
+// last-error slot, outermost frame first (index 0 mirrors c2pa_error_code()/
+// c2pa_last_error() for backward compatibility). Populated by `From<C2paInternalError>`
+// below, alongside `set_last()`, so it always describes the same failure.
+thread_local! {
+    static ERROR_CHAIN: RefCell<Vec<(i32, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns the cause chain recorded for the error last stored in the
+/// `CimplError` last-error slot, or an empty chain if no error is set or a
+/// different error (e.g. a NULL-parameter check) has since overwritten it.
+fn current_error_chain() -> Vec<(i32, String)> {
+    ERROR_CHAIN.with(|cell| {
+        let chain = cell.borrow();
+        match (chain.first(), CimplError::last_message()) {
+            (Some((_, top)), Some(current)) if *top == current => chain.clone(),
+            _ => Vec::new(),
+        }
+    })
+}
+
 impl From<C2paInternalError> for CimplError {
     fn from(e: C2paInternalError) -> Self {
-        let (code, name, msg) = match e {
-            C2paInternalError::C2pa(e) => {
-                let (c, n) = match &e {
-                    c2pa::Error::InvalidAsset(_) => (C2paError::InvalidFormat, "InvalidFormat"),
-                    c2pa::Error::IoError(_) => (C2paError::IoError, "IoError"),
-                    c2pa::Error::BadParam(_) => (C2paError::InvalidSettings, "InvalidSettings"),
-                    _ => (C2paError::ContextError, "ContextError"),
-                };
-                (c, n, format!("{}", e))
-            }
-            C2paInternalError::Json(e) => {
-                (C2paError::SerializationError, "SerializationError", format!("{}", e))
-            }
-            C2paInternalError::Other(msg) => {
-                (C2paError::SerializationError, "SerializationError", msg)
-            }
-        };
-        CimplError::new(code as i32, format!("{}: {}", name, msg))
+        let (code, name, msg, source): (C2paError, &str, String, Option<&dyn std::error::Error>) =
+            match &e {
+                C2paInternalError::C2pa(err) => {
+                    let (c, n) = match err {
+                        c2pa::Error::InvalidAsset(_) => (C2paError::InvalidFormat, "InvalidFormat"),
+                        c2pa::Error::IoError(_) => (C2paError::IoError, "IoError"),
+                        c2pa::Error::BadParam(_) => (C2paError::InvalidSettings, "InvalidSettings"),
+                        _ => (C2paError::ContextError, "ContextError"),
+                    };
+                    (c, n, format!("{}", err), std::error::Error::source(err))
+                }
+                C2paInternalError::Json(err) => (
+                    C2paError::SerializationError,
+                    "SerializationError",
+                    format!("{}", err),
+                    std::error::Error::source(err),
+                ),
+                C2paInternalError::Other(msg) => {
+                    (C2paError::SerializationError, "SerializationError", msg.clone(), None)
+                }
+            };
+
+        let top_message = format!("{}: {}", name, msg);
+
+        // Walk `source()` from the originating error, anyhow-style, so a C
+        // caller can inspect the full cause chain instead of just the
+        // flattened top-level message.
+        let mut chain = vec![(code as i32, top_message.clone())];
+        let mut cause = source;
+        while let Some(c) = cause {
+            chain.push((code as i32, c.to_string()));
+            cause = c.source();
+        }
+        ERROR_CHAIN.with(|cell| *cell.borrow_mut() = chain);
+        emit_log(LogLevel::Warn, &top_message);
+
+        CimplError::LibraryError(code as i32, top_message)
+    }
+}
+
+// ============================================================================
+// Verification Flags
+// ============================================================================
+
+bitflags::bitflags! {
+    /// Typed verification toggles for a [`C2paContext`], as an alternative to
+    /// hand-building a settings JSON/TOML blob for simple on/off checks.
+    ///
+    /// cbindgen emits this as a real header typedef with named constants
+    /// typed as `C2paVerifyFlags` values rather than loose integer
+    /// `#define`s - see `cbindgen.toml`. The header is generated as C, so no
+    /// C++ operator overloads are emitted.
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct C2paVerifyFlags: u32 {
+        /// Run a verification pass immediately after signing.
+        const VERIFY_AFTER_SIGN = 1 << 0;
+        /// Verify the trust chain of the active signer/manifest.
+        const VERIFY_TRUST = 1 << 1;
+        /// Verify the embedded timestamp.
+        const VERIFY_TIMESTAMP = 1 << 2;
+        /// Fetch OCSP responses while verifying trust.
+        const OCSP_FETCH = 1 << 3;
     }
 }
 
@@ -89,12 +224,17 @@ impl From<C2paInternalError> for CimplError {
 /// The pointer never changes, but the inner Context can be replaced to support builder patterns
 pub struct C2paContext {
     inner: c2pa::Context,
+    /// The flags last applied via [`c2pa_context_set_verify_flags`], kept
+    /// alongside `inner` so [`c2pa_context_get_verify_flags`] can read them
+    /// back without needing an accessor on `c2pa::Context`/`Settings`.
+    verify_flags: C2paVerifyFlags,
 }
 
 impl C2paContext {
     fn new() -> Self {
         Self {
             inner: c2pa::Context::new(),
+            verify_flags: C2paVerifyFlags::empty(),
         }
     }
 }
@@ -149,9 +289,13 @@ pub extern "C" fn c2pa_context_with_settings(
     
     // Create new Context with settings and replace the inner one
     ok_or_return!(
-        c2pa::Context::new().with_settings(json).map_err(C2paInternalError::C2pa),
+        c2pa::Context::new()
+            .with_settings(json)
+            .map_err(C2paInternalError::C2pa)
+            .map_err(CimplError::from),
         |new_ctx| {
             ctx_ref.inner = new_ctx;
+            emit_log(LogLevel::Info, "context settings applied from JSON");
             0
         },
         -1
@@ -174,15 +318,51 @@ pub extern "C" fn c2pa_context_with_settings_toml(
     let ctx_ref = deref_mut_or_return_neg!(ctx, C2paContext);
     
     ok_or_return!(
-        c2pa::Context::new().with_settings(toml).map_err(C2paInternalError::C2pa),
+        c2pa::Context::new()
+            .with_settings(toml)
+            .map_err(C2paInternalError::C2pa)
+            .map_err(CimplError::from),
         |new_ctx| {
             ctx_ref.inner = new_ctx;
+            emit_log(LogLevel::Info, "context settings applied from TOML");
             0
         },
         -1
     )
 }
 
+/// Like [`c2pa_context_with_settings`], but also reports a settings failure
+/// through `out_err` (the thread-local last error is set as well) - useful
+/// for hosts that interleave calls across threads or a thread pool, where the
+/// thread-local channel isn't reliably read by the same logical caller that
+/// made the call. A NULL `ctx` or `settings_json` is reported only through
+/// the thread-local last error; `out_err` is left untouched in that case.
+///
+/// `out_err` may be null if the caller doesn't want per-call reporting, in
+/// which case this behaves exactly like `c2pa_context_with_settings`.
+/// Release a populated `out_err` with [`c2pa_error_free`].
+#[no_mangle]
+pub extern "C" fn c2pa_context_with_settings_out_err(
+    ctx: *mut C2paContext,
+    settings_json: *const c_char,
+    out_err: *mut ExternError,
+) -> i32 {
+    let json = cstr_or_return!(settings_json, -1);
+    let ctx_ref = deref_mut_or_return_neg!(ctx, C2paContext);
+
+    let new_ctx = ok_or_return_out_err!(
+        c2pa::Context::new()
+            .with_settings(json)
+            .map_err(C2paInternalError::C2pa)
+            .map_err(CimplError::from),
+        -1,
+        out_err
+    );
+    ctx_ref.inner = new_ctx;
+    emit_log(LogLevel::Info, "context settings applied from JSON");
+    0
+}
+
 /// Free a Context
 ///
 /// # Safety
@@ -238,7 +418,9 @@ pub extern "C" fn c2pa_settings_new() -> *mut C2paSettings {
 pub extern "C" fn c2pa_settings_from_json(json: *const c_char) -> *mut C2paSettings {
     let json_str = cstr_or_return_null!(json);
     let inner = ok_or_return_null!(
-        serde_json::from_str(&json_str).map_err(C2paInternalError::Json)
+        serde_json::from_str(&json_str)
+            .map_err(C2paInternalError::Json)
+            .map_err(CimplError::from)
     );
     let settings = C2paSettings { inner };
     box_tracked!(settings)
@@ -254,7 +436,9 @@ pub extern "C" fn c2pa_settings_from_json(json: *const c_char) -> *mut C2paSetti
 pub extern "C" fn c2pa_settings_from_toml(toml: *const c_char) -> *mut C2paSettings {
     let toml_str = cstr_or_return_null!(toml);
     let inner = ok_or_return_null!(
-        toml::from_str(&toml_str).map_err(|e| C2paInternalError::Other(format!("{}", e)))
+        toml::from_str(&toml_str)
+            .map_err(|e| C2paInternalError::Other(format!("{}", e)))
+            .map_err(CimplError::from)
     );
     let settings = C2paSettings { inner };
     box_tracked!(settings)
@@ -276,7 +460,9 @@ pub extern "C" fn c2pa_settings_from_toml(toml: *const c_char) -> *mut C2paSetti
 pub extern "C" fn c2pa_settings_to_json(settings: *mut C2paSettings) -> *mut c_char {
     let settings_ref = deref_or_return_null!(settings, C2paSettings);
     let json = ok_or_return_null!(
-        serde_json::to_string_pretty(&settings_ref.inner).map_err(C2paInternalError::Json)
+        serde_json::to_string_pretty(&settings_ref.inner)
+            .map_err(C2paInternalError::Json)
+            .map_err(CimplError::from)
     );
     to_c_string(json)
 }
@@ -290,10 +476,86 @@ pub extern "C" fn c2pa_settings_to_toml(settings: *mut C2paSettings) -> *mut c_c
     let toml = ok_or_return_null!(
         toml::to_string_pretty(&settings_ref.inner)
             .map_err(|e| C2paInternalError::Other(format!("{}", e)))
+            .map_err(CimplError::from)
     );
     to_c_string(toml)
 }
 
+/// Diffs two Settings objects, serializing the result as an RFC 6902 JSON
+/// Patch (an array of `{"op", "path", "value"}` add/remove/replace
+/// operations) describing how to transform `base` into `other`.
+///
+/// Returns NULL on error. Caller must free with c2pa_free().
+#[no_mangle]
+pub extern "C" fn c2pa_settings_diff_json(
+    base: *mut C2paSettings,
+    other: *mut C2paSettings,
+) -> *mut c_char {
+    let base_ref = deref_or_return_null!(base, C2paSettings);
+    let other_ref = deref_or_return_null!(other, C2paSettings);
+
+    let base_value = ok_or_return_null!(serde_json::to_value(&base_ref.inner)
+        .map_err(C2paInternalError::Json)
+        .map_err(CimplError::from));
+    let other_value = ok_or_return_null!(serde_json::to_value(&other_ref.inner)
+        .map_err(C2paInternalError::Json)
+        .map_err(CimplError::from));
+
+    let mut ops = Vec::new();
+    diff_json_values("", &base_value, &other_value, &mut ops);
+
+    let json = ok_or_return_null!(serde_json::to_string_pretty(&serde_json::Value::Array(ops))
+        .map_err(C2paInternalError::Json)
+        .map_err(CimplError::from));
+    to_c_string(json)
+}
+
+/// Recursively compares `base` and `other`, appending RFC 6902 JSON Patch
+/// operations (relative to `path`, a JSON Pointer) that transform `base`
+/// into `other`. Objects are diffed key-by-key; same-length arrays are
+/// diffed element-by-element; anything else (differing types, differing
+/// array lengths, or a changed scalar) becomes a single `replace` at `path`.
+fn diff_json_values(
+    path: &str,
+    base: &serde_json::Value,
+    other: &serde_json::Value,
+    ops: &mut Vec<serde_json::Value>,
+) {
+    use serde_json::Value;
+    match (base, other) {
+        (Value::Object(base_map), Value::Object(other_map)) => {
+            for (key, base_val) in base_map {
+                let child_path = format!("{}/{}", path, escape_json_pointer_token(key));
+                match other_map.get(key) {
+                    Some(other_val) => diff_json_values(&child_path, base_val, other_val, ops),
+                    None => ops.push(serde_json::json!({"op": "remove", "path": child_path})),
+                }
+            }
+            for (key, other_val) in other_map {
+                if !base_map.contains_key(key) {
+                    let child_path = format!("{}/{}", path, escape_json_pointer_token(key));
+                    ops.push(serde_json::json!({"op": "add", "path": child_path, "value": other_val}));
+                }
+            }
+        }
+        (Value::Array(base_items), Value::Array(other_items))
+            if base_items.len() == other_items.len() =>
+        {
+            for (i, (b, o)) in base_items.iter().zip(other_items).enumerate() {
+                diff_json_values(&format!("{}/{}", path, i), b, o, ops);
+            }
+        }
+        _ if base != other => {
+            ops.push(serde_json::json!({"op": "replace", "path": path, "value": other}));
+        }
+        _ => {}
+    }
+}
+
+fn escape_json_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
 /// Apply Settings to a Context (builder-style, mutates Context in place)
 ///
 /// This configures the Context with the given Settings.
@@ -313,15 +575,62 @@ pub extern "C" fn c2pa_context_with_settings_obj(
     ok_or_return!(
         c2pa::Context::new()
             .with_settings(settings_ref.inner.clone())
-            .map_err(C2paInternalError::C2pa),
+            .map_err(C2paInternalError::C2pa)
+            .map_err(CimplError::from),
         |new_ctx| {
             ctx_ref.inner = new_ctx;
+            emit_log(LogLevel::Info, "context settings applied from Settings object");
             0
         },
         -1
     )
 }
 
+/// Configure a Context's verification behavior from a [`C2paVerifyFlags`]
+/// bitmask (builder-style, mutates the Context in place).
+///
+/// Unrecognized bits are silently ignored (see `C2paVerifyFlags::from_bits_truncate`),
+/// so callers compiled against an older header remain forward-compatible
+/// with a newer one that defines more flags.
+/// Returns 0 on success, non-zero on error.
+///
+/// # Parameters
+/// - `ctx`: Context to modify
+/// - `flags`: Bitwise-OR of `C2paVerifyFlags` constants
+#[no_mangle]
+pub extern "C" fn c2pa_context_set_verify_flags(ctx: *mut C2paContext, flags: u32) -> i32 {
+    let ctx_ref = deref_mut_or_return_neg!(ctx, C2paContext);
+    let flags = C2paVerifyFlags::from_bits_truncate(flags);
+
+    let mut settings = c2pa::settings::Settings::default();
+    settings.verify.verify_after_sign = flags.contains(C2paVerifyFlags::VERIFY_AFTER_SIGN);
+    settings.verify.verify_trust = flags.contains(C2paVerifyFlags::VERIFY_TRUST);
+    settings.verify.verify_timestamp_trust = flags.contains(C2paVerifyFlags::VERIFY_TIMESTAMP);
+    settings.verify.ocsp_fetch = flags.contains(C2paVerifyFlags::OCSP_FETCH);
+
+    ok_or_return!(
+        c2pa::Context::new()
+            .with_settings(settings)
+            .map_err(C2paInternalError::C2pa)
+            .map_err(CimplError::from),
+        |new_ctx| {
+            ctx_ref.inner = new_ctx;
+            ctx_ref.verify_flags = flags;
+            emit_log(LogLevel::Info, "context verify flags updated");
+            0
+        },
+        -1
+    )
+}
+
+/// Reads back the [`C2paVerifyFlags`] last applied via
+/// [`c2pa_context_set_verify_flags`] (0 if never called, or if `ctx` is NULL).
+#[no_mangle]
+pub extern "C" fn c2pa_context_get_verify_flags(ctx: *mut C2paContext) -> u32 {
+    let ctx_ref = deref_or_return_zero!(ctx, C2paContext);
+    ctx_ref.verify_flags.bits()
+}
+
 /// Free Settings
 ///
 /// # Safety
@@ -341,6 +650,14 @@ pub extern "C" fn c2pa_error_code() -> i32 {
     CimplError::last_code()
 }
 
+/// Releases the `message` allocation of an `ExternError` populated by
+/// [`c2pa_context_with_settings_out_err`]. Safe to call on a zeroed/success
+/// `ExternError` (its `message` is already null).
+#[no_mangle]
+pub extern "C" fn c2pa_error_free(err: ExternError) {
+    cimpl::extern_error_free(err)
+}
+
 /// Gets the error message of the last error (NULL if no error)
 /// Caller must free the returned string with c2pa_free()
 #[no_mangle]
@@ -348,6 +665,90 @@ pub extern "C" fn c2pa_last_error() -> *mut c_char {
     option_to_c_string!(CimplError::last_message())
 }
 
+/// Gets the number of frames in the last error's cause chain (0 if no error).
+///
+/// Index 0 is always the top-level error - the same one `c2pa_error_code()`/
+/// `c2pa_last_error()` report - followed by each underlying `source()` cause,
+/// outermost first. The chain is cleared as soon as a different error is set.
+#[no_mangle]
+pub extern "C" fn c2pa_error_chain_len() -> i32 {
+    current_error_chain().len() as i32
+}
+
+/// Gets the message of one frame of the last error's cause chain.
+///
+/// Returns NULL if `index` is negative or >= `c2pa_error_chain_len()`.
+/// Caller must free the returned string with `c2pa_free()`.
+#[no_mangle]
+pub extern "C" fn c2pa_error_chain_message(index: i32) -> *mut c_char {
+    if index < 0 {
+        return std::ptr::null_mut();
+    }
+    match current_error_chain().get(index as usize) {
+        Some((_, message)) => to_c_string(message.clone()),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Gets the backtrace captured when the last error was set, if any.
+///
+/// Only captured when the `CIMPL_BACKTRACE=1` environment variable is set
+/// (read once, when the first error is recorded, and cached). Returns NULL if capture is disabled, no error
+/// is set, or the platform doesn't support it. Caller must free the returned
+/// string with `c2pa_free()`.
+#[no_mangle]
+pub extern "C" fn c2pa_last_error_backtrace() -> *mut c_char {
+    option_to_c_string!(CimplError::last_backtrace())
+}
+
+/// Serializes the last error into a stable JSON object - `code` (the
+/// [`C2paError`] value), symbolic `name` (e.g. `"InvalidFormat"`), flattened
+/// `message`, and the `chain` of cause frames from [`c2pa_error_chain_message`]
+/// (including the top frame, same order) - so hosts can parse error codes
+/// deterministically instead of scraping `c2pa_last_error()` text.
+///
+/// Returns NULL if no error is set. Caller must free with c2pa_free().
+#[no_mangle]
+pub extern "C" fn c2pa_last_error_json() -> *mut c_char {
+    let Some(message) = CimplError::last_message() else {
+        return std::ptr::null_mut();
+    };
+    let name = message.split_once(": ").map_or("Unknown", |(n, _)| n);
+
+    let mut json = String::from("{\"code\":");
+    json.push_str(&CimplError::last_code().to_string());
+    json.push_str(",\"name\":");
+    push_json_string(&mut json, name);
+    json.push_str(",\"message\":");
+    push_json_string(&mut json, &message);
+    json.push_str(",\"chain\":[");
+    for (i, (_, frame)) in current_error_chain().iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        push_json_string(&mut json, frame);
+    }
+    json.push_str("]}");
+    to_c_string(json)
+}
+
+/// Appends `s` to `out` as a quoted, escaped JSON string literal.
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
 /// Free a string allocated by C2PA functions
 ///
 /// # Safety
@@ -19,7 +19,60 @@ fn main() {
         .expect("Unable to generate bindings")
         .write_to_file(&output_file);
 
+    // Optional second pass: emit a C++ RAII/exception wrapper around the
+    // header above, so consumers don't have to hand-write the
+    // MyStringException/`mystring_free()` glue shown in src/lib.rs's docs.
+    cimpl::cpp_codegen::generate_cpp_wrappers(
+        &PathBuf::from(&crate_dir).join("include").join("mystring.hpp"),
+        &cimpl::cpp_codegen::CppCodegenConfig {
+            header_name: "cimpl_example.h",
+            include_guard: "CIMPL_EXAMPLE_HPP",
+            error_code_fn: "mystring_error_code",
+            error_message_fn: "mystring_last_error",
+            error_message_free_fn: "mystring_string_free",
+            types: &[cimpl::cpp_codegen::OpaqueTypeSpec {
+                class_name: "MyString",
+                c_type: "MyString",
+                create_fn: "mystring_create",
+                create_params: &["const char* initial"],
+                create_args: &["initial"],
+                free_fn: "mystring_free",
+                methods: &[
+                    cimpl::cpp_codegen::MethodSpec {
+                        cpp_name: "value",
+                        c_fn: "mystring_get_value",
+                        params: &[],
+                        args: &[],
+                        return_type: "std::string",
+                        returns_c_string: true,
+                        string_free_fn: "mystring_string_free",
+                    },
+                    cimpl::cpp_codegen::MethodSpec {
+                        cpp_name: "to_uppercase",
+                        c_fn: "mystring_to_uppercase",
+                        params: &[],
+                        args: &[],
+                        return_type: "std::string",
+                        returns_c_string: true,
+                        string_free_fn: "mystring_string_free",
+                    },
+                    cimpl::cpp_codegen::MethodSpec {
+                        cpp_name: "append",
+                        c_fn: "mystring_append",
+                        params: &["const char* suffix"],
+                        args: &["suffix"],
+                        return_type: "int32_t",
+                        returns_c_string: false,
+                        string_free_fn: "",
+                    },
+                ],
+            }],
+        },
+    )
+    .expect("Unable to generate C++ wrappers");
+
     println!("cargo:rerun-if-changed=src/lib.rs");
     println!("cargo:rerun-if-changed=cbindgen.toml");
     println!("cargo:warning=Generated C header at: {}", output_file.display());
+    println!("cargo:warning=Generated C++ wrapper at: {}", crate_dir.clone() + "/include/mystring.hpp");
 }
@@ -112,6 +112,24 @@ pub extern "C" fn vc_from_hex(hex: *const c_char) -> *mut ValueConverter {
     box_tracked!(converter)
 }
 
+/// Create from a standard-alphabet base64 string (can fail if invalid base64,
+/// too large, or empty)
+#[no_mangle]
+pub extern "C" fn vc_from_base64(b64: *const c_char) -> *mut ValueConverter {
+    let b64_str = cstr_or_return_null!(b64);
+    let converter = ok_or_return_null!(ValueConverter::from_base64(&b64_str));
+    box_tracked!(converter)
+}
+
+/// Create from a URL-safe base64 string (can fail if invalid base64, too
+/// large, or empty)
+#[no_mangle]
+pub extern "C" fn vc_from_base64_url(b64: *const c_char) -> *mut ValueConverter {
+    let b64_str = cstr_or_return_null!(b64);
+    let converter = ok_or_return_null!(ValueConverter::from_base64_url(&b64_str));
+    box_tracked!(converter)
+}
+
 // ============================================================================
 // FFI: Conversions (Fallible - these demonstrate Result<T, E>!)
 // ============================================================================
@@ -186,6 +204,20 @@ pub extern "C" fn vc_to_hex(value: *mut ValueConverter) -> *mut c_char {
     to_c_string(converter.to_hex())
 }
 
+/// Convert to a standard-alphabet base64 string (always succeeds)
+#[no_mangle]
+pub extern "C" fn vc_to_base64(value: *mut ValueConverter) -> *mut c_char {
+    let converter = deref_or_return_null!(value, ValueConverter);
+    to_c_string(converter.to_base64())
+}
+
+/// Convert to a URL-safe base64 string (always succeeds)
+#[no_mangle]
+pub extern "C" fn vc_to_base64_url(value: *mut ValueConverter) -> *mut c_char {
+    let converter = deref_or_return_null!(value, ValueConverter);
+    to_c_string(converter.to_base64_url())
+}
+
 /// Get raw bytes with length
 #[no_mangle]
 pub extern "C" fn vc_to_bytes(value: *mut ValueConverter, out_len: *mut usize) -> *const u8 {
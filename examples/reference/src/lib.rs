@@ -22,7 +22,10 @@ pub enum Error {
     
     #[error("invalid hex: {0}")]
     InvalidHex(String),
-    
+
+    #[error("invalid base64: {0}")]
+    InvalidBase64(String),
+
     #[error("buffer too large: got {got} bytes, max {max}")]
     BufferTooLarge { got: usize, max: usize },
     
@@ -139,7 +142,37 @@ impl ValueConverter {
         
         Ok(Self { bytes })
     }
-    
+
+    /// Create from a standard-alphabet base64 string (`+`/`/`, `=` padding required)
+    pub fn from_base64(s: &str) -> Result<Self> {
+        if s.is_empty() {
+            return Err(Error::EmptyValue);
+        }
+        let bytes = decode_base64(s, &BASE64_STD_ALPHABET, true)?;
+        if bytes.len() > MAX_BUFFER_SIZE {
+            return Err(Error::BufferTooLarge {
+                got: bytes.len(),
+                max: MAX_BUFFER_SIZE,
+            });
+        }
+        Ok(Self { bytes })
+    }
+
+    /// Create from a URL-safe base64 string (`-`/`_`, padding optional)
+    pub fn from_base64_url(s: &str) -> Result<Self> {
+        if s.is_empty() {
+            return Err(Error::EmptyValue);
+        }
+        let bytes = decode_base64(s, &BASE64_URL_ALPHABET, false)?;
+        if bytes.len() > MAX_BUFFER_SIZE {
+            return Err(Error::BufferTooLarge {
+                got: bytes.len(),
+                max: MAX_BUFFER_SIZE,
+            });
+        }
+        Ok(Self { bytes })
+    }
+
     /// Convert to signed 32-bit integer (little-endian)
     pub fn to_i32(&self) -> Result<i32> {
         if self.bytes.len() != 4 {
@@ -203,7 +236,17 @@ impl ValueConverter {
     pub fn to_hex(&self) -> String {
         self.bytes.iter().map(|b| format!("{:02x}", b)).collect()
     }
-    
+
+    /// Convert to a standard-alphabet base64 string (`+`/`/`, `=` padded)
+    pub fn to_base64(&self) -> String {
+        encode_base64(&self.bytes, &BASE64_STD_ALPHABET, true)
+    }
+
+    /// Convert to a URL-safe base64 string (`-`/`_`, unpadded)
+    pub fn to_base64_url(&self) -> String {
+        encode_base64(&self.bytes, &BASE64_URL_ALPHABET, false)
+    }
+
     /// Get the size in bytes
     pub fn len(&self) -> usize {
         self.bytes.len()
@@ -215,6 +258,108 @@ impl ValueConverter {
     }
 }
 
+// ============================================================================
+// Base64 Codec
+// ============================================================================
+
+const BASE64_STD_ALPHABET: [u8; 64] = *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_ALPHABET: [u8; 64] = *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Builds the 256-entry decode lookup table for an alphabet: `table[byte]` is
+/// the 6-bit value that character decodes to, or `-1` if the byte isn't part
+/// of the alphabet.
+fn base64_decode_table(alphabet: &[u8; 64]) -> [i8; 256] {
+    let mut table = [-1i8; 256];
+    for (value, &byte) in alphabet.iter().enumerate() {
+        table[byte as usize] = value as i8;
+    }
+    table
+}
+
+/// Encodes `bytes` 3-at-a-time into 4 alphabet characters, padding the final
+/// group with `=` when `pad` is set and the input isn't a multiple of 3.
+fn encode_base64(bytes: &[u8], alphabet: &[u8; 64], pad: bool) -> String {
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for group in bytes.chunks(3) {
+        let n = (group[0] as u32) << 16
+            | (*group.get(1).unwrap_or(&0) as u32) << 8
+            | (*group.get(2).unwrap_or(&0) as u32);
+        out.push(alphabet[((n >> 18) & 0x3f) as usize] as char);
+        out.push(alphabet[((n >> 12) & 0x3f) as usize] as char);
+        match group.len() {
+            1 => {
+                if pad {
+                    out.push('=');
+                    out.push('=');
+                }
+            }
+            2 => {
+                out.push(alphabet[((n >> 6) & 0x3f) as usize] as char);
+                if pad {
+                    out.push('=');
+                }
+            }
+            _ => {
+                out.push(alphabet[((n >> 6) & 0x3f) as usize] as char);
+                out.push(alphabet[(n & 0x3f) as usize] as char);
+            }
+        }
+    }
+    out
+}
+
+/// Decodes a base64 string against `alphabet`. When `require_padding` is
+/// set, the length (including any `=` tail) must be a multiple of 4, so an
+/// unpadded input whose final group is short is rejected rather than
+/// silently accepted; otherwise padding is accepted but never required (the
+/// URL-safe variant).
+fn decode_base64(s: &str, alphabet: &[u8; 64], require_padding: bool) -> Result<Vec<u8>> {
+    let table = base64_decode_table(alphabet);
+    let stripped = s.trim_end_matches('=');
+    let pad_len = s.len() - stripped.len();
+    if pad_len > 2 {
+        return Err(Error::InvalidBase64("too much padding".to_string()));
+    }
+    if require_padding && s.len() % 4 != 0 {
+        return Err(Error::InvalidBase64("incorrect padding".to_string()));
+    }
+    match stripped.len() % 4 {
+        0 => {}
+        1 => return Err(Error::InvalidBase64("invalid length".to_string())),
+        _ => {}
+    }
+
+    let chars: &[u8] = stripped.as_bytes();
+    let mut bytes = Vec::with_capacity(chars.len() / 4 * 3 + 3);
+    for group in chars.chunks(4) {
+        let mut values = [0u8; 4];
+        let mut count = 0;
+        for (slot, &c) in values.iter_mut().zip(group) {
+            let v = table[c as usize];
+            if v < 0 {
+                return Err(Error::InvalidBase64(format!(
+                    "invalid character: {}",
+                    c as char
+                )));
+            }
+            *slot = v as u8;
+            count += 1;
+        }
+        let n = (values[0] as u32) << 18
+            | (values[1] as u32) << 12
+            | (values[2] as u32) << 6
+            | (values[3] as u32);
+        bytes.push((n >> 16) as u8);
+        if count > 2 {
+            bytes.push((n >> 8) as u8);
+        }
+        if count > 3 {
+            bytes.push(n as u8);
+        }
+    }
+    Ok(bytes)
+}
+
 // ============================================================================
 // FFI Module
 // ============================================================================
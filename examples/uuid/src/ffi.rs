@@ -98,6 +98,23 @@ pub extern "C" fn uuid_parse(s: *const c_char) -> *mut Uuid {
     box_tracked!(uuid)
 }
 
+/// Parse a UUID from a string, returning a self-contained result.
+///
+/// Unlike `uuid_parse`, the failure is carried in the returned
+/// `CimplResult` itself (`tag`/`code`/`err_msg`), not only in the
+/// thread-local last error (which is still set too), so interleaved calls
+/// across threads can't cross-contaminate.
+/// On success, `ok_ptr` holds a `Uuid*` that must be freed with `uuid_free()`;
+/// release the result itself with `cimpl_result_free()`.
+///
+/// # Parameters
+/// - `s`: Null-terminated C string containing the UUID
+#[no_mangle]
+pub extern "C" fn uuid_parse_result(s: *const c_char) -> CimplResult {
+    let uuid_str = cstr_or_return!(s, CimplResult::err(cimpl::Error::NullParameter("s")));
+    ok_or_return_result!(Uuid::parse_str(&uuid_str))
+}
+
 /// Create a UUID from raw bytes.
 ///
 /// # Parameters
@@ -116,6 +133,171 @@ pub extern "C" fn uuid_from_bytes(bytes: *const u8) -> *mut Uuid {
     box_tracked!(Uuid::from_bytes(array))  // Direct call to uuid crate
 }
 
+/// Parse multiple newline-delimited UUIDs in one call.
+///
+/// Blank lines are skipped; lines that fail to parse are simply omitted
+/// rather than failing the whole call. Returns NULL if `s` is invalid.
+///
+/// The returned container, and each `Uuid*` it holds, must be freed with
+/// `uuid_free()`.
+///
+/// # Parameters
+/// - `s`: Null-terminated C string of newline-delimited UUIDs
+#[no_mangle]
+pub extern "C" fn uuid_parse_many(s: *const c_char) -> *mut CimplVec<*mut Uuid> {
+    let text = cstr_or_return_null!(s);
+    let uuids: Vec<*mut Uuid> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| Uuid::parse_str(line).ok())
+        .map(|uuid| box_tracked!(uuid))
+        .collect();
+    to_cimpl_vec(uuids)
+}
+
+// ============================================================================
+// Well-Known Namespaces (for v3/v5 namespace-based UUIDs)
+// ============================================================================
+
+/// The DNS namespace UUID, for hashing domain names with `uuid_new_v3`/`uuid_new_v5`.
+#[no_mangle]
+pub extern "C" fn uuid_namespace_dns() -> *mut Uuid {
+    box_tracked!(Uuid::NAMESPACE_DNS)
+}
+
+/// The URL namespace UUID, for hashing URLs with `uuid_new_v3`/`uuid_new_v5`.
+#[no_mangle]
+pub extern "C" fn uuid_namespace_url() -> *mut Uuid {
+    box_tracked!(Uuid::NAMESPACE_URL)
+}
+
+/// The OID namespace UUID, for hashing ISO OIDs with `uuid_new_v3`/`uuid_new_v5`.
+#[no_mangle]
+pub extern "C" fn uuid_namespace_oid() -> *mut Uuid {
+    box_tracked!(Uuid::NAMESPACE_OID)
+}
+
+/// The X.500 DN namespace UUID, for hashing X.500 DNs with `uuid_new_v3`/`uuid_new_v5`.
+#[no_mangle]
+pub extern "C" fn uuid_namespace_x500() -> *mut Uuid {
+    box_tracked!(Uuid::NAMESPACE_X500)
+}
+
+// ============================================================================
+// Namespace-Based UUIDs (v3 - MD5, v5 - SHA-1)
+// ============================================================================
+
+/// Generate a deterministic Version 3 (MD5) UUID from a namespace and name.
+///
+/// Hashing the same namespace and name always produces the same UUID.
+/// Returns NULL if either pointer is invalid.
+/// The returned UUID must be freed with `uuid_free()`.
+///
+/// # Parameters
+/// - `namespace`: Pointer to the namespace UUID (see `uuid_namespace_*`)
+/// - `name`: Pointer to the name bytes (need not be valid UTF-8)
+/// - `name_len`: Length of `name` in bytes
+#[no_mangle]
+pub extern "C" fn uuid_new_v3(
+    namespace: *const Uuid,
+    name: *const u8,
+    name_len: usize,
+) -> *mut Uuid {
+    let namespace = deref_or_return_null!(namespace, Uuid);
+    // SAFETY: caller guarantees `name` is valid for `name_len` bytes.
+    let name = ok_or_return_null!(unsafe { safe_slice_from_raw_parts(name, name_len, "name") });
+    box_tracked!(Uuid::new_v3(namespace, name))
+}
+
+/// Generate a deterministic Version 5 (SHA-1) UUID from a namespace and name.
+///
+/// Hashing the same namespace and name always produces the same UUID.
+/// Returns NULL if either pointer is invalid.
+/// The returned UUID must be freed with `uuid_free()`.
+///
+/// # Parameters
+/// - `namespace`: Pointer to the namespace UUID (see `uuid_namespace_*`)
+/// - `name`: Pointer to the name bytes (need not be valid UTF-8)
+/// - `name_len`: Length of `name` in bytes
+#[no_mangle]
+pub extern "C" fn uuid_new_v5(
+    namespace: *const Uuid,
+    name: *const u8,
+    name_len: usize,
+) -> *mut Uuid {
+    let namespace = deref_or_return_null!(namespace, Uuid);
+    // SAFETY: caller guarantees `name` is valid for `name_len` bytes.
+    let name = ok_or_return_null!(unsafe { safe_slice_from_raw_parts(name, name_len, "name") });
+    box_tracked!(Uuid::new_v5(namespace, name))
+}
+
+// ============================================================================
+// Time-Based and Time-Ordered UUIDs (v1, v6, v7)
+// ============================================================================
+
+/// Reads a 6-byte node identifier from a raw pointer.
+fn node_id_from_ptr(node_id: *const u8) -> Result<[u8; 6], cimpl::Error> {
+    // SAFETY: caller guarantees `node_id` is valid for 6 bytes.
+    let bytes = unsafe { safe_slice_from_raw_parts(node_id, 6, "node_id") }?;
+    let mut array = [0u8; 6];
+    array.copy_from_slice(bytes);
+    Ok(array)
+}
+
+/// Generate a Version 1 UUID (Gregorian timestamp + node id).
+///
+/// Returns NULL if `node_id` is invalid.
+/// The returned UUID must be freed with `uuid_free()`.
+///
+/// # Parameters
+/// - `node_id`: Pointer to a 6-byte node identifier
+#[no_mangle]
+pub extern "C" fn uuid_new_v1(node_id: *const u8) -> *mut Uuid {
+    let node_id = ok_or_return_null!(node_id_from_ptr(node_id));
+    let ts = uuid::Timestamp::now(uuid::NoContext);
+    box_tracked!(Uuid::new_v1(ts, &node_id))
+}
+
+/// Generate a Version 6 UUID: field-compatible with v1, but reordered so
+/// lexical sort order matches creation order.
+///
+/// Returns NULL if `node_id` is invalid.
+/// The returned UUID must be freed with `uuid_free()`.
+///
+/// # Parameters
+/// - `node_id`: Pointer to a 6-byte node identifier
+#[no_mangle]
+pub extern "C" fn uuid_new_v6(node_id: *const u8) -> *mut Uuid {
+    let node_id = ok_or_return_null!(node_id_from_ptr(node_id));
+    let ts = uuid::Timestamp::now(uuid::NoContext);
+    box_tracked!(Uuid::new_v6(ts, &node_id))
+}
+
+/// Generate a Version 7 UUID (Unix timestamp + random), sortable by creation time.
+///
+/// Returns NULL on allocation failure.
+/// The returned UUID must be freed with `uuid_free()`.
+#[no_mangle]
+pub extern "C" fn uuid_new_v7() -> *mut Uuid {
+    box_tracked!(Uuid::now_v7())
+}
+
+/// Generate a Version 7 UUID from an explicit timestamp instead of the current time.
+///
+/// Useful for deterministic tests or backfilling sortable ids for existing records.
+/// Returns NULL on allocation failure.
+/// The returned UUID must be freed with `uuid_free()`.
+///
+/// # Parameters
+/// - `secs`: Unix timestamp, whole seconds
+/// - `nanos`: Sub-second nanoseconds (0-999,999,999)
+#[no_mangle]
+pub extern "C" fn uuid_new_v7_from_timestamp(secs: u64, nanos: u32) -> *mut Uuid {
+    let ts = uuid::Timestamp::from_unix(uuid::NoContext, secs, nanos);
+    box_tracked!(Uuid::new_v7(ts))
+}
+
 // ============================================================================
 // UUID Conversion and Access
 // ============================================================================
@@ -165,6 +347,41 @@ pub extern "C" fn uuid_to_urn(uuid: *const Uuid) -> *mut c_char {
     to_c_string(uuid_ref.urn().to_string())  // Direct call
 }
 
+/// Format selector for `uuid_to_string_fmt`: hyphenated, e.g. "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8".
+pub const UUID_FMT_HYPHENATED: i32 = 0;
+/// Format selector for `uuid_to_string_fmt`: simple (no hyphens), e.g. "a1a2a3a4b1b2c1c2d1d2d3d4d5d6d7d8".
+pub const UUID_FMT_SIMPLE: i32 = 1;
+/// Format selector for `uuid_to_string_fmt`: braced, e.g. "{a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8}".
+pub const UUID_FMT_BRACED: i32 = 2;
+/// Format selector for `uuid_to_string_fmt`: URN, e.g. "urn:uuid:a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8".
+pub const UUID_FMT_URN: i32 = 3;
+
+/// Convert a UUID to a string, selecting the rendering with `fmt`.
+///
+/// A single entry point for every canonical string form the uuid crate
+/// supports, instead of a separate function per format.
+///
+/// Returns NULL if the UUID pointer is invalid or `fmt` is out of range.
+/// The returned string must be freed with `uuid_free()`.
+///
+/// # Parameters
+/// - `uuid`: Pointer to UUID
+/// - `fmt`: One of `UUID_FMT_HYPHENATED`, `UUID_FMT_SIMPLE`, `UUID_FMT_BRACED`, `UUID_FMT_URN`
+#[no_mangle]
+pub extern "C" fn uuid_to_string_fmt(uuid: *const Uuid, fmt: i32) -> *mut c_char {
+    let uuid_ref = deref_or_return_null!(uuid, Uuid);
+    match fmt {
+        UUID_FMT_HYPHENATED => to_c_string(uuid_ref.hyphenated().to_string()),
+        UUID_FMT_SIMPLE => to_c_string(uuid_ref.simple().to_string()),
+        UUID_FMT_BRACED => to_c_string(uuid_ref.braced().to_string()),
+        UUID_FMT_URN => to_c_string(uuid_ref.urn().to_string()),
+        _ => {
+            cimpl::Error::Other(format!("Unknown UUID format selector: {fmt}")).set_last();
+            std::ptr::null_mut()
+        }
+    }
+}
+
 /// Get the raw bytes of a UUID.
 ///
 /// Copies the 16 bytes of the UUID into the provided buffer.
@@ -186,6 +403,38 @@ pub extern "C" fn uuid_as_bytes(uuid: *const Uuid, out_bytes: *mut u8) -> bool {
     true
 }
 
+/// Write the hyphenated string form of a UUID into a caller-provided buffer,
+/// without allocating.
+///
+/// Writes at most `cap` bytes (including the trailing NUL). `*out_written` is
+/// always set to the number of bytes the full string needs (37, including
+/// the NUL), so callers can query the size with `cap == 0` and retry.
+///
+/// # Returns
+/// * `0` on success
+/// * `ErrorCode::NullParameter` if `uuid` is invalid
+/// * `ErrorCode::BufferTooSmall` if `cap` is too small - nothing is written
+///
+/// # Parameters
+/// - `uuid`: Pointer to UUID
+/// - `buf`: Caller-provided buffer of at least `cap` bytes
+/// - `cap`: Capacity of `buf`, in bytes
+/// - `out_written`: Set to the number of bytes the full string needs
+#[no_mangle]
+pub extern "C" fn uuid_to_string_buf(
+    uuid: *const Uuid,
+    buf: *mut c_char,
+    cap: usize,
+    out_written: *mut usize,
+) -> i32 {
+    let uuid_ref = deref_or_return!(uuid, Uuid, cimpl::ErrorCode::NullParameter as i32);
+
+    let mut encode_buf = [0u8; uuid::fmt::Hyphenated::LENGTH];
+    let s = uuid_ref.hyphenated().encode_lower(&mut encode_buf);
+    // SAFETY: `buf` is valid for `cap` bytes and `out_written` for one usize, per caller contract.
+    unsafe { write_cstr_to_buf(s, buf, cap, out_written) }
+}
+
 // ============================================================================
 // UUID Comparison
 // ============================================================================
@@ -251,6 +500,49 @@ pub extern "C" fn uuid_last_error() -> *mut c_char {
     option_to_c_string!(cimpl::Error::last_message())
 }
 
+/// Get the cause chain of the last error, outermost cause first.
+///
+/// Returns a newline-delimited C string, or NULL if no error occurred or the
+/// error has no captured chain (only errors converted via `Error::from_error`
+/// carry one). The returned string must be freed with `uuid_free()`.
+#[no_mangle]
+pub extern "C" fn uuid_last_error_chain() -> *mut c_char {
+    let chain = cimpl::Error::last_chain();
+    if chain.is_empty() {
+        std::ptr::null_mut()
+    } else {
+        to_c_string(chain.join("\n"))
+    }
+}
+
+/// Get the backtrace captured for the last error, if any.
+///
+/// Returns NULL unless `CIMPL_BACKTRACE=1` was set in the environment when
+/// the first error was recorded (the variable is read once and cached). The returned string must be freed with `uuid_free()`.
+#[no_mangle]
+pub extern "C" fn uuid_last_error_backtrace() -> *mut c_char {
+    option_to_c_string!(cimpl::Error::last_backtrace())
+}
+
+/// Get the stable numeric code of the last error.
+///
+/// Returns 0 (no error) if no error occurred. Bindings can `switch` on this
+/// instead of parsing the variant out of `uuid_last_error()`'s message.
+#[no_mangle]
+pub extern "C" fn uuid_last_error_code() -> u32 {
+    cimpl::Error::last_code_u32()
+}
+
+/// Get the last error as a structured JSON document.
+///
+/// Returns `{"variant":...,"details":...,"code":...,"chain":[...],"backtrace":...}`
+/// (`chain`/`backtrace` omitted when empty), or NULL if no error occurred.
+/// The returned string must be freed with `uuid_free()`.
+#[no_mangle]
+pub extern "C" fn uuid_last_error_json() -> *mut c_char {
+    option_to_c_string!(cimpl::Error::last_json())
+}
+
 // ============================================================================
 // Memory Management
 // ============================================================================
@@ -30,8 +30,9 @@ use std::os::raw::c_char;
 
 use cimpl::{
     box_tracked, cimpl_free, cstr_or_return_null,
-    deref_or_return_null, error::CimplError, ok_or_return_false, ok_or_return_null, 
-    option_to_c_string, to_c_bytes, to_c_string, 
+    deref_or_return_null, error::CimplError, ok_or_return_false, ok_or_return_null,
+    option_to_c_string, to_c_bytes, to_c_string,
+    bytestring::CByteString,
     Error,
 };
 
@@ -197,18 +198,24 @@ fn count_words(input: &str) -> usize {
 // ============================================================================
 
 /// Encodes text using ROT13 cipher
-/// Tests: cstr_or_return_null!, to_c_string!
+/// Tests: cstr_lossy_or_return_null!, to_c_string! - defined over text with
+/// occasional invalid bytes, so ill-formed UTF-8 is substituted rather than
+/// rejected
 #[no_mangle]
 pub extern "C" fn secret_rot13(input: *const c_char) -> *mut c_char {
-    let text = cstr_or_return_null!(input);
+    use cimpl::cstr_lossy_or_return_null;
+    let text = cstr_lossy_or_return_null!(input);
     to_c_string(rot13(&text))
 }
 
 /// Reverses the input string
-/// Tests: cstr_or_return_null!, to_c_string!
+/// Tests: cstr_lossy_or_return_null!, to_c_string! - defined over text with
+/// occasional invalid bytes, so ill-formed UTF-8 is substituted rather than
+/// rejected
 #[no_mangle]
 pub extern "C" fn secret_reverse(input: *const c_char) -> *mut c_char {
-    let text = cstr_or_return_null!(input);
+    use cimpl::cstr_lossy_or_return_null;
+    let text = cstr_lossy_or_return_null!(input);
     to_c_string(text.chars().rev().collect::<String>())
 }
 
@@ -302,11 +309,13 @@ pub extern "C" fn secret_is_valid_hex(input: *const c_char) -> bool {
 // ============================================================================
 
 /// Counts characters in string
-/// Tests: cstr_or_return! with 0 on error
+/// Tests: cstr_lossy_or_return! with 0 on error - defined over text with
+/// occasional invalid bytes, so ill-formed UTF-8 is substituted rather than
+/// rejected
 #[no_mangle]
 pub extern "C" fn secret_count_chars(input: *const c_char) -> usize {
-    use cimpl::cstr_or_return;
-    let text = cstr_or_return!(input, 0);
+    use cimpl::cstr_lossy_or_return;
+    let text = cstr_lossy_or_return!(input, 0);
     text.chars().count()
 }
 
@@ -361,7 +370,7 @@ pub extern "C" fn secret_to_bytes(input: *const c_char, out_len: *mut usize) ->
 #[no_mangle]
 pub extern "C" fn secret_from_bytes(data: *const u8, len: usize) -> *mut c_char {
     if data.is_null() {
-        Error::from(CimplError::NullParameter("data".to_string())).set_last();
+        Error::from(CimplError::NullParameter("data")).set_last();
         return std::ptr::null_mut();
     }
     
@@ -373,6 +382,15 @@ pub extern "C" fn secret_from_bytes(data: *const u8, len: usize) -> *mut c_char
     to_c_string(text)
 }
 
+/// Converts byte array to an opaque `CByteString`, without requiring valid
+/// UTF-8 - unlike `secret_from_bytes`, arbitrary binary payloads round-trip
+/// losslessly; use `cbytestring_debug` to render them for logs.
+/// Tests: byte array handling, `cimpl::bytestring::cbytestring_new`
+#[no_mangle]
+pub extern "C" fn secret_from_bytes_raw(data: *const u8, len: usize) -> *mut CByteString {
+    cimpl::bytestring::cbytestring_new(data, len)
+}
+
 // ============================================================================
 // FFI Functions: Struct Operations (SecretMessage)
 // ============================================================================
@@ -393,6 +411,37 @@ pub extern "C" fn message_new(content: *const c_char, encoding: *const c_char) -
     box_tracked!(msg)
 }
 
+/// Creates a new secret message from Windows wide (UTF-16) strings.
+/// Tests: wstr_or_return_null!, box_tracked! - opt-in alternative to
+/// `message_new` for callers whose strings only exist as `*const u16`
+/// (e.g. Windows). Stored as lossy UTF-8 like the rest of `SecretMessage`,
+/// so unpaired surrogates in `content`/`encoding` become U+FFFD here; use
+/// `cimpl::Wtf8Buf` directly if lossless storage is needed.
+#[no_mangle]
+pub extern "C" fn message_new_w(content: *const u16, encoding: *const u16) -> *mut SecretMessage {
+    use cimpl::wstr_or_return_null;
+
+    let content_buf = wstr_or_return_null!(content);
+    let encoding_buf = wstr_or_return_null!(encoding);
+
+    let msg = SecretMessage {
+        content: content_buf.to_string_lossy(),
+        encoding: encoding_buf.to_string_lossy(),
+        metadata: HashMap::new(),
+    };
+
+    box_tracked!(msg)
+}
+
+/// Gets the content of a message as a Windows wide (UTF-16) string.
+/// Tests: deref_or_return_null!, to_c_wstring!
+#[no_mangle]
+pub extern "C" fn message_get_content_w(msg: *mut SecretMessage) -> *mut u16 {
+    let message = deref_or_return_null!(msg, SecretMessage);
+    let units: Vec<u16> = message.content.encode_utf16().collect();
+    cimpl::to_c_wstring(&cimpl::Wtf8Buf::from_wide(&units))
+}
+
 /// Gets the content of a message
 /// Tests: deref_or_return_null!, to_c_string!
 #[no_mangle]
@@ -0,0 +1,141 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Opaque, non-UTF-8 byte-string handle.
+//!
+//! `cstr_or_return!`/`to_c_string()` require valid UTF-8 on both sides of the
+//! boundary - a binding that needs to carry arbitrary binary payloads (a
+//! hash, a compressed blob, text in an unknown encoding) has nowhere to put
+//! them without either rejecting the data or silently losing bytes. A
+//! [`CByteString`] is just a tracked, boxed `Vec<u8>` with no such
+//! requirement: construct it with [`cbytestring_new`], use the ordinary
+//! [`box_tracked!`]/[`deref_or_return_null!`]/[`crate::cimpl_free`] machinery
+//! every other tracked pointer uses, and render it for logs with
+//! [`cbytestring_debug`] when you want a human-readable, always-valid-UTF-8
+//! view without losing the original bytes.
+
+use std::os::raw::c_char;
+
+use crate::utils::to_c_string;
+
+/// An opaque, tracked byte string. No UTF-8 requirement - holds exactly the
+/// bytes it was constructed with.
+///
+/// Allocate with [`cbytestring_new`]; free with [`crate::cimpl_free`], same
+/// as any other `box_tracked!` pointer.
+pub struct CByteString(Vec<u8>);
+
+impl CByteString {
+    /// Wraps `bytes` in a `CByteString`, taking ownership.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// The raw bytes, unchanged from construction.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Number of bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Renders `bytes` as a human-readable, always-valid-UTF-8 `String`:
+/// printable ASCII is copied verbatim, `\\`, `\n`, `\t`, and `\r` are escaped
+/// with their usual backslash form, and every other byte is escaped as
+/// `\xNN`.
+pub fn escape_bytes(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for &b in bytes {
+        match b {
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            b'\t' => out.push_str("\\t"),
+            b'\r' => out.push_str("\\r"),
+            0x20..=0x7e => out.push(b as char),
+            _ => out.push_str(&format!("\\x{:02x}", b)),
+        }
+    }
+    out
+}
+
+/// Creates a [`CByteString`] from a raw byte buffer.
+///
+/// Returns NULL if `data` is null (length-0, non-null buffers are allowed
+/// and produce an empty `CByteString`).
+#[no_mangle]
+pub extern "C" fn cbytestring_new(data: *const u8, len: usize) -> *mut CByteString {
+    if data.is_null() {
+        crate::Error::NullParameter("data").set_last();
+        return std::ptr::null_mut();
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(data, len) }.to_vec();
+    crate::box_tracked!(CByteString::new(bytes))
+}
+
+/// Number of bytes in `s`. Returns 0 on a null/invalid pointer.
+#[no_mangle]
+pub extern "C" fn cbytestring_len(s: *mut CByteString) -> usize {
+    crate::deref_or_return_zero!(s, CByteString).len()
+}
+
+/// Borrows the raw bytes of `s`, writing the length to `out_len`. The
+/// returned pointer is owned by `s` and only valid until `s` is freed -
+/// unlike [`crate::to_c_bytes`], this does not allocate a copy.
+#[no_mangle]
+pub extern "C" fn cbytestring_bytes(s: *mut CByteString, out_len: *mut usize) -> *const u8 {
+    let byte_string = crate::deref_or_return_null!(s, CByteString);
+    if !out_len.is_null() {
+        unsafe { *out_len = byte_string.len() };
+    }
+    byte_string.as_bytes().as_ptr()
+}
+
+/// Renders `s` as an escaped, human-readable C string - see [`escape_bytes`].
+/// Returns NULL on a null/invalid pointer.
+#[no_mangle]
+pub extern "C" fn cbytestring_debug(s: *mut CByteString) -> *mut c_char {
+    let byte_string = crate::deref_or_return_null!(s, CByteString);
+    to_c_string(escape_bytes(byte_string.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_non_printable_and_special_bytes() {
+        let bytes = b"hi\n\t\r\\\x00\xff ok";
+        assert_eq!(escape_bytes(bytes), "hi\\n\\t\\r\\\\\\x00\\xff ok");
+    }
+
+    #[test]
+    fn leaves_printable_ascii_untouched() {
+        assert_eq!(escape_bytes(b"Hello, World! 123"), "Hello, World! 123");
+    }
+
+    #[test]
+    fn round_trips_through_new_and_as_bytes() {
+        let s = CByteString::new(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(s.as_bytes(), &[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(s.len(), 4);
+        assert!(!s.is_empty());
+    }
+}
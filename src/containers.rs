@@ -0,0 +1,162 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Owned, `repr(C)` container types, modeled on the C-mapped container types
+//! used by projects like LDK's generated bindings.
+//!
+//! A bare `*const u8` plus a separate `*mut usize` out-parameter is fragile -
+//! callers can forget the length, and there's no way to return a list of
+//! objects at all. These containers bundle the pointer with its length (and,
+//! for [`CimplVec`], its capacity) into a single owned value that's tracked
+//! in the same registry as `box_tracked!`/`arc_tracked!`, so [`crate::cimpl_free`]
+//! recognizes and releases them like any other tracked pointer.
+
+use std::os::raw::c_uchar;
+
+use crate::utils::track_with_cleanup;
+
+/// An owned byte buffer returned across FFI: a pointer plus its length.
+///
+/// Allocate with [`to_cimpl_bytes`]. The returned `*mut CimplBytes` (and the
+/// buffer it points to) are freed together by [`crate::cimpl_free`].
+#[repr(C)]
+pub struct CimplBytes {
+    pub ptr: *mut c_uchar,
+    pub len: usize,
+}
+
+/// Converts a `Vec<u8>` into a tracked, owned [`CimplBytes`] pointer.
+///
+/// The returned pointer must be freed exactly once via [`crate::cimpl_free`],
+/// which releases both the `CimplBytes` struct and the byte buffer it points
+/// to.
+pub fn to_cimpl_bytes(bytes: Vec<u8>) -> *mut CimplBytes {
+    let len = bytes.len();
+    let data_ptr = Box::into_raw(bytes.into_boxed_slice()) as *mut c_uchar;
+    let container = Box::into_raw(Box::new(CimplBytes { ptr: data_ptr, len }));
+
+    let data_ptr_val = data_ptr as usize;
+    let cleanup = move || unsafe {
+        drop(Box::from_raw(std::slice::from_raw_parts_mut(
+            data_ptr_val as *mut c_uchar,
+            len,
+        ) as *mut [c_uchar]));
+    };
+    track_with_cleanup(container, cleanup);
+    container
+}
+
+/// An owned, contiguous array of `T` returned across FFI: a pointer, length,
+/// and capacity, mirroring `Vec<T>`'s own raw parts.
+///
+/// Allocate with [`to_cimpl_vec`]. Commonly used to return arrays of tracked
+/// handles or primitives in a single call, instead of requiring the caller
+/// to poll an index-based accessor.
+///
+/// `cap` is always equal to `len` - [`to_cimpl_vec`] reconstructs the backing
+/// allocation from a boxed slice rather than the original `Vec`'s raw parts,
+/// so there's no stale pre-shrink capacity to round-trip. The field stays
+/// for callers that already destructure `{ptr, len, cap}`.
+#[repr(C)]
+pub struct CimplVec<T> {
+    pub ptr: *mut T,
+    pub len: usize,
+    pub cap: usize,
+}
+
+/// Converts a `Vec<T>` into a tracked, owned [`CimplVec`] pointer.
+///
+/// The returned pointer must be freed exactly once via [`crate::cimpl_free`],
+/// which releases both the `CimplVec` struct and the backing allocation.
+///
+/// Reads `len` and shrinks to a boxed slice *before* deriving `cap`, the same
+/// pattern [`to_cimpl_bytes`] uses - `into_boxed_slice()` reallocates down to
+/// `len` whenever the `Vec` was over-allocated (virtually always, for a
+/// `Vec` built via `.collect()`/repeated `.push()`), so capturing `cap` from
+/// the original `Vec` before that reallocation would free the returned
+/// pointer with a `Layout` that doesn't match its actual allocation.
+pub fn to_cimpl_vec<T: 'static>(items: Vec<T>) -> *mut CimplVec<T> {
+    let len = items.len();
+    let data_ptr = Box::into_raw(items.into_boxed_slice()) as *mut T;
+    let container = Box::into_raw(Box::new(CimplVec {
+        ptr: data_ptr,
+        len,
+        cap: len,
+    }));
+
+    let data_ptr_val = data_ptr as usize;
+    let cleanup = move || unsafe {
+        drop(Box::from_raw(std::slice::from_raw_parts_mut(
+            data_ptr_val as *mut T,
+            len,
+        ) as *mut [T]));
+    };
+    track_with_cleanup(container, cleanup);
+    container
+}
+
+/// A nullable scalar returned by value across FFI, for types that have no
+/// spare sentinel to signal "no value" (e.g. a `u64` handle where every bit
+/// pattern is a legal handle).
+///
+/// Unlike [`CimplBytes`]/[`CimplVec`], this is returned by value, not behind
+/// a pointer - there's no separate allocation to track or free.
+#[repr(C)]
+pub struct CimplOption<T> {
+    pub is_some: bool,
+    pub value: T,
+}
+
+impl<T: Default> CimplOption<T> {
+    /// Builds a `CimplOption` from a Rust `Option<T>`. `None` becomes
+    /// `{ is_some: false, value: T::default() }`, so C code that ignores
+    /// `is_some` still reads a well-defined value.
+    pub fn from_option(opt: Option<T>) -> Self {
+        match opt {
+            Some(value) => Self {
+                is_some: true,
+                value,
+            },
+            None => Self {
+                is_some: false,
+                value: T::default(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::cimple_free;
+
+    #[test]
+    fn to_cimpl_vec_frees_cleanly_when_the_source_vec_over_allocated() {
+        // `cap` (16) deliberately outlives `len` (2) here - `to_cimpl_vec`
+        // must derive `cap` from the boxed slice's own reallocation, not
+        // from this `Vec`'s original capacity, or the free below uses the
+        // wrong `Layout` and corrupts the allocator.
+        let mut items = Vec::with_capacity(16);
+        items.push(1u32);
+        items.push(2u32);
+
+        let container = to_cimpl_vec(items);
+        unsafe {
+            assert_eq!((*container).len, 2);
+            assert_eq!((*container).cap, 2);
+            assert_eq!(*(*container).ptr, 1);
+            assert_eq!(*(*container).ptr.add(1), 2);
+            assert_eq!(cimple_free(container as *mut std::ffi::c_void), 0);
+        }
+    }
+}
@@ -0,0 +1,349 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Companion C++ RAII/exception wrapper generation, layered on top of the C
+//! header `cbindgen` already emits from a `build.rs`.
+//!
+//! A handful of examples in this repo (see `mystring_*` in `example/`) show
+//! users hand-writing a `MyStringException` class and `*_free()` calls
+//! around a generated header. This module turns that by-hand pattern into a
+//! second, optional `build.rs` step: describe each opaque type's
+//! constructor/destructor/methods once, and [`generate_cpp_wrappers`] emits
+//! a `.hpp` with a class that does it for you.
+//!
+//! Unlike `cbindgen`, this doesn't parse the generated header - it takes an
+//! explicit [`CppCodegenConfig`] instead, the same declarative-table
+//! convention [`crate::error::ErrorTable`] and [`register_error_codes!`] use.
+//! Scraping cbindgen's output text would be brittle (its exact formatting
+//! isn't a stable contract) and would need a real C parser; a small
+//! hand-written table costs one `build.rs` edit per opaque type and never
+//! goes stale silently.
+//!
+//! ```rust,ignore
+//! // build.rs, after the existing cbindgen::Builder::generate() call:
+//! cimpl::cpp_codegen::generate_cpp_wrappers(
+//!     &PathBuf::from(&crate_dir).join("include").join("mystring.hpp"),
+//!     &cimpl::cpp_codegen::CppCodegenConfig {
+//!         header_name: "cimpl_example.h",
+//!         include_guard: "CIMPL_EXAMPLE_HPP",
+//!         error_code_fn: "mystring_error_code",
+//!         error_message_fn: "mystring_last_error",
+//!         error_message_free_fn: "mystring_string_free",
+//!         types: &[cimpl::cpp_codegen::OpaqueTypeSpec {
+//!             class_name: "MyString",
+//!             c_type: "MyString",
+//!             create_fn: "mystring_create",
+//!             create_params: &["const char* initial"],
+//!             create_args: &["initial"],
+//!             free_fn: "mystring_free",
+//!             methods: &[cimpl::cpp_codegen::MethodSpec {
+//!                 cpp_name: "value",
+//!                 c_fn: "mystring_get_value",
+//!                 params: &[],
+//!                 args: &[],
+//!                 return_type: "std::string",
+//!                 returns_c_string: true,
+//!                 string_free_fn: "mystring_string_free",
+//!             }],
+//!         }],
+//!     },
+//! )
+//! .expect("failed to generate C++ wrappers");
+//! ```
+
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+/// One method to wrap on an [`OpaqueTypeSpec`], beyond its constructor and
+/// destructor.
+pub struct MethodSpec {
+    /// The generated C++ method name.
+    pub cpp_name: &'static str,
+    /// The `extern "C"` function this method calls, taking the wrapped
+    /// pointer as its first argument.
+    pub c_fn: &'static str,
+    /// C++ parameter declarations, e.g. `["const char* suffix"]`.
+    pub params: &'static [&'static str],
+    /// Argument names to forward to `c_fn`, in the same order as `params`.
+    pub args: &'static [&'static str],
+    /// The C++ return type, e.g. `"std::string"`, `"bool"`, `"int32_t"`.
+    /// Ignored when `returns_c_string` is set - that always returns
+    /// `std::string`.
+    pub return_type: &'static str,
+    /// Whether `c_fn` returns an owned `char*` that should be copied into a
+    /// `std::string` and then freed, rather than returned as-is. A null
+    /// result throws [`CimplException`] (emitted as a C++ class of the same
+    /// name in the generated header).
+    pub returns_c_string: bool,
+    /// The function that frees the `char*` `c_fn` returns, when
+    /// `returns_c_string` is set.
+    pub string_free_fn: &'static str,
+}
+
+/// One opaque type to wrap, with its constructor, destructor, and methods.
+pub struct OpaqueTypeSpec {
+    /// The generated C++ class name.
+    pub class_name: &'static str,
+    /// The C struct type `cbindgen` emitted for this opaque type.
+    pub c_type: &'static str,
+    /// The `extern "C"` constructor function, returning `{c_type}*` (null on
+    /// failure, which throws [`CimplException`]).
+    pub create_fn: &'static str,
+    /// C++ parameter declarations for the generated constructor.
+    pub create_params: &'static [&'static str],
+    /// Argument names to forward to `create_fn`, in the same order as
+    /// `create_params`.
+    pub create_args: &'static [&'static str],
+    /// The `extern "C"` function the destructor calls to free the pointer.
+    pub free_fn: &'static str,
+    /// Methods to wrap beyond the constructor/destructor.
+    pub methods: &'static [MethodSpec],
+}
+
+/// Describes the whole `.hpp` to generate: the shared exception type's error
+/// accessors, and every opaque type to wrap.
+pub struct CppCodegenConfig {
+    /// The cbindgen-generated C header to `#include`, e.g. `"mylib.h"`.
+    pub header_name: &'static str,
+    /// The `#ifndef`/`#define` include-guard token.
+    pub include_guard: &'static str,
+    /// The `extern "C"` function returning the last error's numeric code.
+    pub error_code_fn: &'static str,
+    /// The `extern "C"` function returning the last error's message as an
+    /// owned `char*` (or null if none is set).
+    pub error_message_fn: &'static str,
+    /// The function that frees the string `error_message_fn` returns.
+    pub error_message_free_fn: &'static str,
+    /// The opaque types to generate RAII wrapper classes for.
+    pub types: &'static [OpaqueTypeSpec],
+}
+
+/// Generates a C++ header at `output_path` with one RAII wrapper class per
+/// [`OpaqueTypeSpec`] in `config`, plus a shared `CimplException`. Intended
+/// to run from `build.rs`, right after the `cbindgen::Builder` call that
+/// produces `config.header_name`.
+///
+/// Each wrapper class's constructor calls the type's `create_fn` and throws
+/// `CimplException` if it returns null; the destructor calls `free_fn`;
+/// methods with `returns_c_string` set copy their result into a
+/// `std::string` and free the original buffer via `string_free_fn`, also
+/// throwing on a null result. The class is move-only - copying an owned
+/// handle would let two destructors free the same pointer.
+pub fn generate_cpp_wrappers(output_path: &Path, config: &CppCodegenConfig) -> io::Result<()> {
+    let rendered = render_cpp_wrappers(config);
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(output_path, rendered)
+}
+
+/// Renders the `.hpp` contents described by `config`. Split out from
+/// [`generate_cpp_wrappers`] so it can be unit-tested without touching the
+/// filesystem.
+fn render_cpp_wrappers(config: &CppCodegenConfig) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "// Generated by cimpl's C++ codegen pass. Do not edit by hand.");
+    let _ = writeln!(out, "#ifndef {}", config.include_guard);
+    let _ = writeln!(out, "#define {}", config.include_guard);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "#include \"{}\"", config.header_name);
+    let _ = writeln!(out, "#include <cstdint>");
+    let _ = writeln!(out, "#include <stdexcept>");
+    let _ = writeln!(out, "#include <string>");
+    let _ = writeln!(out, "#include <utility>");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "class CimplException : public std::exception {{");
+    let _ = writeln!(out, "    int32_t code_;");
+    let _ = writeln!(out, "    std::string message_;");
+    let _ = writeln!(out, "public:");
+    let _ = writeln!(out, "    CimplException() : code_({}()) {{", config.error_code_fn);
+    let _ = writeln!(out, "        char* msg = {}();", config.error_message_fn);
+    let _ = writeln!(out, "        if (msg) {{");
+    let _ = writeln!(out, "            message_ = msg;");
+    let _ = writeln!(out, "            {}(msg);", config.error_message_free_fn);
+    let _ = writeln!(out, "        }}");
+    let _ = writeln!(out, "    }}");
+    let _ = writeln!(
+        out,
+        "    const char* what() const noexcept override {{ return message_.c_str(); }}"
+    );
+    let _ = writeln!(out, "    int32_t code() const noexcept {{ return code_; }}");
+    let _ = writeln!(out, "}};");
+
+    for ty in config.types {
+        render_type(&mut out, ty);
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "#endif // {}", config.include_guard);
+    out
+}
+
+fn render_type(out: &mut String, ty: &OpaqueTypeSpec) {
+    let _ = writeln!(out);
+    let _ = writeln!(out, "class {} {{", ty.class_name);
+    let _ = writeln!(out, "    {}* ptr_;", ty.c_type);
+    let _ = writeln!(out, "public:");
+
+    let _ = writeln!(
+        out,
+        "    explicit {}({}) {{",
+        ty.class_name,
+        ty.create_params.join(", ")
+    );
+    let _ = writeln!(
+        out,
+        "        ptr_ = {}({});",
+        ty.create_fn,
+        ty.create_args.join(", ")
+    );
+    let _ = writeln!(out, "        if (!ptr_) {{ throw CimplException(); }}");
+    let _ = writeln!(out, "    }}");
+    let _ = writeln!(out);
+
+    let _ = writeln!(out, "    ~{}() {{", ty.class_name);
+    let _ = writeln!(out, "        if (ptr_) {{ {}(ptr_); }}", ty.free_fn);
+    let _ = writeln!(out, "    }}");
+    let _ = writeln!(out);
+
+    let _ = writeln!(out, "    {}(const {}&) = delete;", ty.class_name, ty.class_name);
+    let _ = writeln!(
+        out,
+        "    {}& operator=(const {}&) = delete;",
+        ty.class_name, ty.class_name
+    );
+    let _ = writeln!(out);
+
+    let _ = writeln!(
+        out,
+        "    {}({}&& other) noexcept : ptr_(other.ptr_) {{ other.ptr_ = nullptr; }}",
+        ty.class_name, ty.class_name
+    );
+    let _ = writeln!(
+        out,
+        "    {}& operator=({}&& other) noexcept {{",
+        ty.class_name, ty.class_name
+    );
+    let _ = writeln!(out, "        if (this != &other) {{");
+    let _ = writeln!(out, "            if (ptr_) {{ {}(ptr_); }}", ty.free_fn);
+    let _ = writeln!(out, "            ptr_ = other.ptr_;");
+    let _ = writeln!(out, "            other.ptr_ = nullptr;");
+    let _ = writeln!(out, "        }}");
+    let _ = writeln!(out, "        return *this;");
+    let _ = writeln!(out, "    }}");
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "    {}* raw() const noexcept {{ return ptr_; }}", ty.c_type);
+
+    for method in ty.methods {
+        render_method(out, method);
+    }
+
+    let _ = writeln!(out, "}};");
+}
+
+fn render_method(out: &mut String, method: &MethodSpec) {
+    let return_type = if method.returns_c_string {
+        "std::string"
+    } else {
+        method.return_type
+    };
+    let mut call_args = vec!["ptr_".to_string()];
+    call_args.extend(method.args.iter().map(|s| s.to_string()));
+
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "    {} {}({}) const {{",
+        return_type,
+        method.cpp_name,
+        method.params.join(", ")
+    );
+    if method.returns_c_string {
+        let _ = writeln!(out, "        char* result = {}({});", method.c_fn, call_args.join(", "));
+        let _ = writeln!(out, "        if (!result) {{ throw CimplException(); }}");
+        let _ = writeln!(out, "        std::string value(result);");
+        let _ = writeln!(out, "        {}(result);", method.string_free_fn);
+        let _ = writeln!(out, "        return value;");
+    } else {
+        let _ = writeln!(out, "        return {}({});", method.c_fn, call_args.join(", "));
+    }
+    let _ = writeln!(out, "    }}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_config() -> CppCodegenConfig {
+        CppCodegenConfig {
+            header_name: "cimpl_example.h",
+            include_guard: "CIMPL_EXAMPLE_HPP",
+            error_code_fn: "mystring_error_code",
+            error_message_fn: "mystring_last_error",
+            error_message_free_fn: "mystring_string_free",
+            types: &[OpaqueTypeSpec {
+                class_name: "MyString",
+                c_type: "MyString",
+                create_fn: "mystring_create",
+                create_params: &["const char* initial"],
+                create_args: &["initial"],
+                free_fn: "mystring_free",
+                methods: &[
+                    MethodSpec {
+                        cpp_name: "value",
+                        c_fn: "mystring_get_value",
+                        params: &[],
+                        args: &[],
+                        return_type: "std::string",
+                        returns_c_string: true,
+                        string_free_fn: "mystring_string_free",
+                    },
+                    MethodSpec {
+                        cpp_name: "append",
+                        c_fn: "mystring_append",
+                        params: &["const char* suffix"],
+                        args: &["suffix"],
+                        return_type: "int32_t",
+                        returns_c_string: false,
+                        string_free_fn: "",
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn renders_constructor_destructor_and_methods() {
+        let rendered = render_cpp_wrappers(&example_config());
+        assert!(rendered.contains("class MyString {"));
+        assert!(rendered.contains("explicit MyString(const char* initial) {"));
+        assert!(rendered.contains("ptr_ = mystring_create(initial);"));
+        assert!(rendered.contains("~MyString() {"));
+        assert!(rendered.contains("if (ptr_) { mystring_free(ptr_); }"));
+        assert!(rendered.contains("std::string value(result);"));
+        assert!(rendered.contains("mystring_string_free(result);"));
+        assert!(rendered.contains("int32_t append(const char* suffix) const {"));
+        assert!(rendered.contains("return mystring_append(ptr_, suffix);"));
+        assert!(rendered.contains("class CimplException"));
+    }
+
+    #[test]
+    fn include_guard_wraps_the_whole_file() {
+        let rendered = render_cpp_wrappers(&example_config());
+        assert!(rendered.starts_with("// Generated by cimpl's C++ codegen pass"));
+        assert!(rendered.contains("#ifndef CIMPL_EXAMPLE_HPP"));
+        assert!(rendered.trim_end().ends_with("#endif // CIMPL_EXAMPLE_HPP"));
+    }
+}
@@ -11,8 +11,13 @@
 // specific language governing permissions and limitations under
 // each license.
 
+#[cfg(not(feature = "no_std"))]
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{OnceLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
+#[cfg(feature = "no_std")]
+use crate::no_std_support::ErrorSink as _;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -26,8 +31,198 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub type ErrorTable<E> = &'static [(fn(&E) -> bool, &'static str, i32)];
 
 // LAST_ERROR handling borrowed from Copyright (c) 2018 Michael Bryan
+//
+// Under the `no_std` feature, both slots are backed by
+// `no_std_support::SpinErrorSink` instead, since thread-locals aren't
+// available. Backtrace capture additionally requires `std::backtrace`, so
+// it's compiled out entirely under `no_std` - `last_backtrace()` just
+// always returns `None`.
+#[cfg(not(feature = "no_std"))]
 thread_local! {
     static LAST_ERROR: RefCell<Option<Error>> = const { RefCell::new(None) };
+    static LAST_BACKTRACE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Whether `CIMPL_BACKTRACE=1` was set, mirroring the `RUST_BACKTRACE` convention.
+/// Read once and cached, so the common (disabled) case costs one atomic load.
+fn backtrace_capture_enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| std::env::var("CIMPL_BACKTRACE").as_deref() == Ok("1"))
+}
+
+/// Recovers a poisoned lock instead of propagating the panic, same pattern as
+/// `handle.rs`'s `read_lock`/`write_lock` and `utils.rs`'s `lock()`. The
+/// registries below are reachable from arbitrary call sites, including
+/// `extern "C"` entry points, so a single panic while one is held must not
+/// wedge every later call into it for the rest of the process.
+fn read_lock<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Write-lock counterpart to [`read_lock`].
+fn write_lock<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Registry mapping a `Wrapped` error's variant name to a stable `u32` code,
+/// populated by downstream crates via [`register_error_code`]. Codes 1-99 are
+/// reserved for the built-in variants (see [`ErrorCode`]); downstream crates
+/// should register starting at 1000, matching the [`ErrorCode`] convention.
+fn code_registry() -> &'static RwLock<HashMap<String, u32>> {
+    static CODE_REGISTRY: OnceLock<RwLock<HashMap<String, u32>>> = OnceLock::new();
+    CODE_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers a stable `u32` code for errors of the given variant name, so that
+/// [`Error::code()`] can resolve a code for errors converted via
+/// [`Error::from_error`] instead of always falling back to `ErrorCode::Other`.
+///
+/// Call once at startup, before converting any errors of that variant -
+/// typically via the [`register_error_codes!`] macro.
+pub fn register_error_code(variant: impl Into<String>, code: u32) {
+    write_lock(code_registry()).insert(variant.into(), code);
+}
+
+/// One entry in the runtime error-code registry: a stable code, its variant
+/// name, and a message template, in registration order. Populated by
+/// [`register_error_codes!`]/[`define_error_codes!`] and enumerable from C
+/// via [`cimpl_error_code_count()`]/[`cimpl_error_code_at()`], so a binding
+/// generator can synthesize one exception class per code without scraping
+/// source or a generated header.
+struct ErrorCodeEntry {
+    code: i32,
+    name: String,
+    message_template: String,
+}
+
+/// The built-in cimpl codes, registered before anything a downstream crate
+/// adds, in the same order as [`ErrorCode`] (skipping `Ok`, which is the
+/// no-error sentinel rather than a reportable error).
+fn builtin_error_code_entries() -> Vec<ErrorCodeEntry> {
+    vec![
+        ErrorCodeEntry {
+            code: ErrorCode::NullParameter as i32,
+            name: "NullParameter".to_string(),
+            message_template: "NullParameter: {0}".to_string(),
+        },
+        ErrorCodeEntry {
+            code: ErrorCode::StringTooLong as i32,
+            name: "StringTooLong".to_string(),
+            message_template: "StringTooLong: {0}".to_string(),
+        },
+        ErrorCodeEntry {
+            code: ErrorCode::InvalidHandle as i32,
+            name: "InvalidHandle".to_string(),
+            message_template: "InvalidHandle: {0}".to_string(),
+        },
+        ErrorCodeEntry {
+            code: ErrorCode::WrongHandleType as i32,
+            name: "WrongHandleType".to_string(),
+            message_template: "WrongHandleType: {0}".to_string(),
+        },
+        ErrorCodeEntry {
+            code: ErrorCode::Other as i32,
+            name: "Other".to_string(),
+            message_template: "Other: {0}".to_string(),
+        },
+        ErrorCodeEntry {
+            code: ErrorCode::Panic as i32,
+            name: "Panic".to_string(),
+            message_template: "Panic: {0}".to_string(),
+        },
+        ErrorCodeEntry {
+            code: ErrorCode::BufferTooSmall as i32,
+            name: "BufferTooSmall".to_string(),
+            message_template: "BufferTooSmall: needs {0} bytes".to_string(),
+        },
+        ErrorCodeEntry {
+            code: ErrorCode::InteriorNul as i32,
+            name: "InteriorNul".to_string(),
+            message_template: "InteriorNul: NUL byte at offset {0}".to_string(),
+        },
+        ErrorCodeEntry {
+            code: ErrorCode::InvalidUtf8 as i32,
+            name: "InvalidUtf8".to_string(),
+            message_template: "InvalidUtf8: {0}".to_string(),
+        },
+    ]
+}
+
+fn error_code_registry() -> &'static RwLock<Vec<ErrorCodeEntry>> {
+    static REGISTRY: OnceLock<RwLock<Vec<ErrorCodeEntry>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(builtin_error_code_entries()))
+}
+
+/// Registers a code in the runtime registry consumed by
+/// [`cimpl_error_code_count()`]/[`cimpl_error_code_at()`], and also in the
+/// variant-to-code map [`register_error_code`] maintains for
+/// [`Error::code()`] - one call keeps both in sync. Typically called via
+/// [`register_error_codes!`]/[`define_error_codes!`] rather than directly.
+pub fn register_error_code_entry(
+    code: i32,
+    name: impl Into<String>,
+    message_template: impl Into<String>,
+) {
+    let name = name.into();
+    register_error_code(name.clone(), code as u32);
+    write_lock(error_code_registry()).push(ErrorCodeEntry {
+        code,
+        name,
+        message_template: message_template.into(),
+    });
+}
+
+/// Returns the number of codes currently in the runtime error-code registry
+/// (the built-in cimpl codes plus any registered via
+/// [`register_error_codes!`]/[`define_error_codes!`]). Pair with
+/// [`cimpl_error_code_at()`] to enumerate them all.
+#[no_mangle]
+pub extern "C" fn cimpl_error_code_count() -> usize {
+    read_lock(error_code_registry()).len()
+}
+
+/// Writes the `index`-th registered error code's numeric code into
+/// `*out_code` and allocates its variant name as a C string into
+/// `*out_name` (via [`crate::utils::to_c_string`] - free it with
+/// [`crate::utils::cimple_free`]).
+///
+/// # Returns
+/// - 0 on success
+/// - -1 if `index` is out of range, or `out_code`/`out_name` is null (neither
+///   out-param is written in that case)
+///
+/// # Safety
+/// `out_code` and `out_name` must each be valid, properly aligned, writable
+/// pointers of the appropriate type.
+#[no_mangle]
+pub unsafe extern "C" fn cimpl_error_code_at(
+    index: usize,
+    out_code: *mut i32,
+    out_name: *mut *mut std::os::raw::c_char,
+) -> i32 {
+    if out_code.is_null() || out_name.is_null() {
+        return -1;
+    }
+    match read_lock(error_code_registry()).get(index) {
+        Some(entry) => {
+            *out_code = entry.code;
+            *out_name = crate::utils::to_c_string(entry.name.clone());
+            0
+        }
+        None => -1,
+    }
+}
+
+/// Returns the `index`-th registered error code's message template (e.g.
+/// `"NullParameter: {0}"`) as a newly allocated C string, or null if `index`
+/// is out of range. Companion to [`cimpl_error_code_at()`] for bindings that
+/// also want the human-readable template, not just the code and name.
+#[no_mangle]
+pub extern "C" fn cimpl_error_code_message_at(index: usize) -> *mut std::os::raw::c_char {
+    match read_lock(error_code_registry()).get(index) {
+        Some(entry) => crate::utils::to_c_string(entry.message_template.clone()),
+        None => std::ptr::null_mut(),
+    }
 }
 
 /// Error codes for FFI - enables language bindings to create typed exceptions
@@ -122,33 +317,301 @@ pub enum ErrorCode {
     WrongHandleType = 4,
     /// Other error occurred
     Other = 5,
-    // 6-99: Reserved for future cimpl library errors
+    /// A Rust panic was caught at the FFI boundary
+    Panic = 6,
+    /// A caller-provided buffer was too small to hold the encoded value
+    BufferTooSmall = 7,
+    /// A string passed to a C string conversion contained an interior NUL byte
+    InteriorNul = 8,
+    /// A C string's bytes were not valid UTF-8
+    InvalidUtf8 = 9,
+    // 10-99: Reserved for future cimpl library errors
     // 100+: Available for library-specific custom errors
 }
 
+/// Maximum number of causes appended to a [`Error::Wrapped`] error's
+/// rendered message (via [`Error::from_error`]), so a pathological or cyclic
+/// `source()` chain can't produce an unbounded display string. [`Error::chain`]
+/// still returns exactly the causes captured here - both are capped at this
+/// same depth.
+pub const MAX_CHAIN_DEPTH: usize = 8;
+
+/// Joins a captured cause chain onto the end of a `Wrapped` error's message,
+/// e.g. `": cause1: cause2"`, capped at [`MAX_CHAIN_DEPTH`] entries. Empty
+/// for errors with no captured causes, so plain `from_error` conversions with
+/// no `source()` render exactly as before.
+fn render_chain(chain: &[String]) -> String {
+    let mut rendered = String::new();
+    for cause in chain.iter().take(MAX_CHAIN_DEPTH) {
+        rendered.push_str(": ");
+        rendered.push_str(cause);
+    }
+    rendered
+}
+
+/// Walks `e.source()` until it returns `None` or [`MAX_CHAIN_DEPTH`] causes
+/// have been collected. Shared by [`Error::from_error`], [`Error::from_table`]
+/// and [`Error::from_mapper`] so all three surface the same cause chain.
+fn capture_chain<E: std::error::Error>(e: &E) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut source = std::error::Error::source(e);
+    while let Some(s) = source {
+        if chain.len() >= MAX_CHAIN_DEPTH {
+            break;
+        }
+        chain.push(s.to_string());
+        source = s.source();
+    }
+    chain
+}
+
 #[derive(Error, Debug)]
 /// Defines all possible FFI errors
 pub enum Error {
+    /// A required parameter was NULL.
+    ///
+    /// Stores `&'static str` rather than `String`: the parameter name always
+    /// comes from `stringify!()` at the macro call site, so this variant
+    /// never allocates even though it fires on every failed null check.
     #[error("NullParameter: {0}")]
-    NullParameter(String),
+    NullParameter(&'static str),
+    /// A C string exceeded the configured maximum length.
+    ///
+    /// Same `&'static str` reasoning as `NullParameter` - no allocation on
+    /// the hot validation-failure path.
     #[error("StringTooLong: {0}")]
-    StringTooLong(String),
+    StringTooLong(&'static str),
     #[error("InvalidHandle: {0}")]
     InvalidHandle(u64),
     #[error("WrongHandleType: {0}")]
     WrongHandleType(u64),
     #[error("Other: {0}")]
     Other(String),
+    #[error("Panic: {0}")]
+    Panic(String),
+    /// A caller-provided buffer was too small. Carries the number of bytes
+    /// the full value needs, so callers can query the size and retry with a
+    /// larger buffer instead of guessing.
+    #[error("BufferTooSmall: needs {0} bytes")]
+    BufferTooSmall(usize),
+    /// A string passed to [`crate::utils::to_c_string`] contained an interior
+    /// NUL byte at the stored offset - a C string cannot represent that, and
+    /// truncating it silently would be a correctness hazard for user data.
+    #[error("InteriorNul: NUL byte at offset {0}")]
+    InteriorNul(usize),
+    /// A C string's bytes were not valid UTF-8.
+    ///
+    /// Same `&'static str` reasoning as `NullParameter` - no allocation on
+    /// the hot validation-failure path.
+    #[error("InvalidUtf8: {0}")]
+    InvalidUtf8(&'static str),
     #[error("{1}")]
     LibraryError(i32, String),
+    /// An error converted from an external `std::error::Error` via [`Error::from_error`],
+    /// carrying the `source()` chain the external error was built from. The
+    /// rendered message appends the chain (capped at [`MAX_CHAIN_DEPTH`]) as
+    /// `"variant: message: cause1: cause2"` so the root cause survives
+    /// crossing the FFI boundary as plain text, not just via [`Error::chain`].
+    #[error("{variant}: {message}{}", render_chain(chain))]
+    Wrapped {
+        variant: String,
+        message: String,
+        chain: Vec<String>,
+    },
+    /// An error annotated with context describing the FFI operation that was
+    /// in progress, via [`context!`]. `source` is the underlying error
+    /// unchanged - `code_as_i32()`/`code()`/`variant()` all delegate to it,
+    /// so switching on the numeric code still works the same regardless of
+    /// how much context has been layered on. Only the rendered message
+    /// (`to_string()`/`details()`) changes, recursing through nested
+    /// `Context`s so a deep call chain reads as
+    /// `"while parsing manifest: invalid utf-8"` instead of just the leaf
+    /// message.
+    #[error("{msg}: {source}")]
+    Context {
+        msg: String,
+        #[source]
+        source: Box<Error>,
+    },
+}
+
+/// Out-parameter for per-call error reporting, as an alternative to the
+/// thread-local last-error slot.
+///
+/// `code`/`message` mirror [`Error::code_as_i32()`] and [`Error::to_string()`]:
+/// a successful call leaves `code` at 0 and `message` null. A failing call
+/// sets `code` to the numeric [`ErrorCode`] and allocates `message` via
+/// [`crate::to_c_string()`], so the caller must free it with
+/// [`crate::cimpl_free()`].
+///
+/// Unlike the thread-local last error, this travels with the call itself, so
+/// it stays correct when calls are interleaved across threads or a thread
+/// pool.
+#[repr(C)]
+pub struct ExternError {
+    pub code: i32,
+    pub message: *mut std::os::raw::c_char,
+}
+
+impl ExternError {
+    /// An `ExternError` representing success: `code == 0`, `message == NULL`.
+    pub fn success() -> Self {
+        Self {
+            code: 0,
+            message: std::ptr::null_mut(),
+        }
+    }
+}
+
+/// Alias for [`ExternError`], matching the name some binding-generator
+/// templates expect. Identical layout and behavior - there is only one
+/// out-parameter error struct in cimpl.
+pub type CExternError = ExternError;
+
+/// A self-contained, by-value success/error result, as an alternative to
+/// `ExternError` and the thread-local last error for callers that want a
+/// single return value with no side channel at all (useful for managed-language
+/// bindings, where reading a second out-parameter or a thread-local is
+/// awkward).
+///
+/// Build with [`CimplResult::ok`]/[`CimplResult::err`] (or the
+/// [`crate::ok_or_return_result!`] macro), and release with
+/// [`cimpl_result_free`](crate::error::cimpl_result_free) once the caller is
+/// done reading it.
+#[repr(C)]
+pub struct CimplResult {
+    /// `0` ([`CimplResult::TAG_OK`]) if `ok_ptr` is populated, `1`
+    /// ([`CimplResult::TAG_ERR`]) if `code`/`err_msg` are populated.
+    pub tag: u8,
+    /// The error's [`ErrorCode`]/stable code. `0` when `tag == TAG_OK`.
+    pub code: i32,
+    /// The success value, already tracked like any `box_tracked!` pointer.
+    /// Null when `tag == TAG_ERR`.
+    pub ok_ptr: *mut std::ffi::c_void,
+    /// An owned, allocated error message. Null when `tag == TAG_OK`.
+    pub err_msg: *mut std::os::raw::c_char,
+}
+
+impl CimplResult {
+    pub const TAG_OK: u8 = 0;
+    pub const TAG_ERR: u8 = 1;
+
+    /// Builds a success result from an already-tracked pointer (e.g. the
+    /// output of `box_tracked!`).
+    pub fn ok(ok_ptr: *mut std::ffi::c_void) -> Self {
+        Self {
+            tag: Self::TAG_OK,
+            code: 0,
+            ok_ptr,
+            err_msg: std::ptr::null_mut(),
+        }
+    }
+
+    /// Builds an error result, allocating `err_msg` and also setting the
+    /// thread-local last error for callers still using that convention.
+    pub fn err(e: Error) -> Self {
+        let code = e.code_as_i32();
+        let err_msg = crate::utils::to_c_string(e.to_string());
+        e.set_last();
+        Self {
+            tag: Self::TAG_ERR,
+            code,
+            ok_ptr: std::ptr::null_mut(),
+            err_msg,
+        }
+    }
+}
+
+/// Releases the `err_msg` allocation of a [`CimplResult`], if populated.
+///
+/// Does NOT free `ok_ptr` - on success, that pointer is owned by the caller
+/// exactly as if it had been returned directly (e.g. from `box_tracked!`),
+/// and must be freed the normal way (typically via [`crate::cimpl_free`])
+/// once the caller is done with it.
+#[no_mangle]
+pub extern "C" fn cimpl_result_free(result: CimplResult) {
+    if !result.err_msg.is_null() {
+        crate::utils::cimple_free(result.err_msg as *mut std::ffi::c_void);
+    }
+}
+
+/// Releases the `message` allocation of an [`ExternError`] written by
+/// [`Error::write_extern_error`]/[`crate::ok_or_return_out_err!`], if populated.
+///
+/// Safe to call on a zeroed/`ExternError::success()` value - `message` is
+/// null in that case and nothing happens. Does not attempt to free the
+/// `ExternError` itself, which callers typically hold by value or on the
+/// stack, not behind a pointer cimpl allocated.
+#[no_mangle]
+pub extern "C" fn extern_error_free(err: ExternError) {
+    if !err.message.is_null() {
+        crate::utils::cimple_free(err.message as *mut std::ffi::c_void);
+    }
+}
+
+/// Alias for [`extern_error_free`], matching the name some binding-generator
+/// templates expect.
+#[no_mangle]
+pub extern "C" fn cimpl_error_free(err: ExternError) {
+    extern_error_free(err)
 }
 
 impl Error {
+    /// Writes this error into an out-parameter `ExternError`.
+    ///
+    /// Does nothing if `out_err` is null, so callers that don't want
+    /// per-call reporting can simply pass null. Also sets the thread-local
+    /// last error, so `*_out_err` macros remain compatible with code that
+    /// still reads `last_code()`/`last_message()`.
+    ///
+    /// # Safety
+    /// `out_err`, if non-null, must point to a valid, writable `ExternError`.
+    pub fn write_extern_error(self, out_err: *mut ExternError) {
+        if !out_err.is_null() {
+            let code = self.code_as_i32();
+            let message = crate::utils::to_c_string(self.to_string());
+            unsafe {
+                (*out_err).code = code;
+                (*out_err).message = message;
+            }
+        }
+        self.set_last();
+    }
+
+    /// Alias for [`Error::write_extern_error`].
+    pub fn write_into(self, out_err: *mut ExternError) {
+        self.write_extern_error(out_err)
+    }
+
     /// Returns the last error as String
+    #[cfg(not(feature = "no_std"))]
     pub fn last_message() -> Option<String> {
         LAST_ERROR.with(|prev| prev.borrow().as_ref().map(|e| e.to_string()))
     }
 
+    /// Returns the last error as String
+    #[cfg(feature = "no_std")]
+    pub fn last_message() -> Option<String> {
+        crate::no_std_support::LAST_ERROR.with(|e| e.map(|e| e.to_string()))
+    }
+
+    /// Returns the formatted backtrace captured for the last error, if
+    /// `CIMPL_BACKTRACE=1` was set when it was recorded via [`Error::set_last`].
+    ///
+    /// Returns `None` when capture is disabled, no error is set, or the
+    /// platform/build doesn't support capturing frames. Always `None` under
+    /// the `no_std` feature - `std::backtrace` isn't available there.
+    #[cfg(not(feature = "no_std"))]
+    pub fn last_backtrace() -> Option<String> {
+        LAST_BACKTRACE.with(|prev| prev.borrow().clone())
+    }
+
+    /// See the std version above - always `None` under `no_std`.
+    #[cfg(feature = "no_std")]
+    pub fn last_backtrace() -> Option<String> {
+        None
+    }
+
     /// Returns the last error code
     ///
     /// This is useful for creating typed exceptions in language bindings.
@@ -158,13 +621,22 @@ impl Error {
     /// - 0: No error
     /// - 1-99: Core cimpl infrastructure errors
     /// - 100+: Library-specific errors
+    #[cfg(not(feature = "no_std"))]
     pub fn last_code() -> i32 {
         LAST_ERROR.with(|prev| prev.borrow().as_ref().map(|e| e.code_as_i32()).unwrap_or(0))
     }
 
+    /// Returns the last error code. See the std version above.
+    #[cfg(feature = "no_std")]
+    pub fn last_code() -> i32 {
+        crate::no_std_support::LAST_ERROR.with(|e| e.map(|e| e.code_as_i32()).unwrap_or(0))
+    }
+
     /// Gets the error code for this error as an i32
     ///
     /// Maps each Error variant to its corresponding error code for FFI use.
+    /// For `Wrapped` errors, resolves through the same registry as
+    /// [`Error::code()`] rather than always returning `ErrorCode::Other`.
     pub fn code_as_i32(&self) -> i32 {
         match self {
             Error::NullParameter(_) => ErrorCode::NullParameter as i32,
@@ -172,16 +644,223 @@ impl Error {
             Error::InvalidHandle(_) => ErrorCode::InvalidHandle as i32,
             Error::WrongHandleType(_) => ErrorCode::WrongHandleType as i32,
             Error::Other(_) => ErrorCode::Other as i32,
+            Error::Panic(_) => ErrorCode::Panic as i32,
+            Error::BufferTooSmall(_) => ErrorCode::BufferTooSmall as i32,
+            Error::InteriorNul(_) => ErrorCode::InteriorNul as i32,
+            Error::InvalidUtf8(_) => ErrorCode::InvalidUtf8 as i32,
             Error::LibraryError(code, _) => *code,
+            Error::Wrapped { .. } => self.code() as i32,
+            Error::Context { source, .. } => source.code_as_i32(),
+        }
+    }
+
+    /// Returns the stable `u32` error code for this error.
+    ///
+    /// Built-in variants use the fixed block defined by [`ErrorCode`] (1-99).
+    /// `Wrapped` errors (from [`Error::from_error`]) look up their variant
+    /// name in the registry populated by [`register_error_code`], falling
+    /// back to `ErrorCode::Other` if the downstream crate never registered
+    /// a code for that variant.
+    pub fn code(&self) -> u32 {
+        match self {
+            Error::Wrapped { variant, .. } => read_lock(code_registry())
+                .get(variant)
+                .copied()
+                .unwrap_or(ErrorCode::Other as u32),
+            _ => self.code_as_i32() as u32,
+        }
+    }
+
+    /// Returns the `u32` code of the last error set via [`Error::set_last`],
+    /// or `ErrorCode::Ok` (0) if no error is set. See [`Error::code()`].
+    #[cfg(not(feature = "no_std"))]
+    pub fn last_code_u32() -> u32 {
+        LAST_ERROR.with(|prev| prev.borrow().as_ref().map(|e| e.code()).unwrap_or(0))
+    }
+
+    /// Returns the `u32` code of the last error. See the std version above.
+    #[cfg(feature = "no_std")]
+    pub fn last_code_u32() -> u32 {
+        crate::no_std_support::LAST_ERROR.with(|e| e.map(|e| e.code()).unwrap_or(0))
+    }
+
+    /// Converts an external `std::error::Error` into a cimpl `Error`.
+    ///
+    /// Extracts the variant name from `Debug` output (same heuristic as
+    /// [`Error::from_mapper`]'s callers typically apply by hand) and the
+    /// message from `Display`, then walks `source()` until it returns `None`
+    /// (or [`MAX_CHAIN_DEPTH`] causes have been collected) to capture the
+    /// cause chain - recovered via [`Error::chain()`], and also folded into
+    /// `to_string()`'s rendered message so the root cause isn't lost by the
+    /// time it reaches a C caller that only reads the message text.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// impl From<MyError> for cimpl::Error {
+    ///     fn from(e: MyError) -> Self {
+    ///         cimpl::Error::from_error(e)
+    ///     }
+    /// }
+    /// ```
+    pub fn from_error<E: std::error::Error>(e: E) -> Self {
+        let debug = format!("{:?}", e);
+        let variant = debug
+            .split(['(', '{'])
+            .next()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or("Unknown")
+            .to_string();
+
+        let chain = capture_chain(&e);
+
+        Error::Wrapped {
+            variant,
+            message: e.to_string(),
+            chain,
+        }
+    }
+
+    /// Returns the cause chain captured by [`Error::from_error`], outermost
+    /// cause first. Empty for errors not built via `from_error`, or built
+    /// from a source with no further `source()`.
+    pub fn chain(&self) -> impl Iterator<Item = &str> + '_ {
+        let chain: &[String] = match self {
+            Error::Wrapped { chain, .. } => chain.as_slice(),
+            _ => &[],
+        };
+        chain.iter().map(String::as_str)
+    }
+
+    /// Returns the deepest (root) cause in the chain, if any was captured.
+    pub fn root_cause(&self) -> Option<&str> {
+        self.chain().last()
+    }
+
+    /// Returns the cause chain of the last error set via [`Error::set_last`],
+    /// outermost cause first. Empty if no error is set or it has no chain.
+    #[cfg(not(feature = "no_std"))]
+    pub fn last_chain() -> Vec<String> {
+        LAST_ERROR.with(|prev| {
+            prev.borrow()
+                .as_ref()
+                .map(|e| e.chain().map(str::to_string).collect())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the cause chain of the last error. See the std version above.
+    #[cfg(feature = "no_std")]
+    pub fn last_chain() -> Vec<String> {
+        crate::no_std_support::LAST_ERROR.with(|e| {
+            e.map(|e| e.chain().map(str::to_string).collect())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the variant name, e.g. `"NullParameter"`, or the captured
+    /// variant name for errors built via [`Error::from_error`].
+    pub fn variant(&self) -> &str {
+        match self {
+            Error::NullParameter(_) => "NullParameter",
+            Error::StringTooLong(_) => "StringTooLong",
+            Error::InvalidHandle(_) => "InvalidHandle",
+            Error::WrongHandleType(_) => "WrongHandleType",
+            Error::Other(_) => "Other",
+            Error::Panic(_) => "Panic",
+            Error::BufferTooSmall(_) => "BufferTooSmall",
+            Error::InteriorNul(_) => "InteriorNul",
+            Error::InvalidUtf8(_) => "InvalidUtf8",
+            Error::LibraryError(_, _) => "LibraryError",
+            Error::Wrapped { variant, .. } => variant,
+            Error::Context { source, .. } => source.variant(),
         }
     }
 
+    /// Returns the message details, without the variant prefix `to_string()`
+    /// would include. Materializes a `String` lazily - only the variants that
+    /// already own one avoid an allocation here.
+    pub fn details(&self) -> String {
+        match self {
+            Error::NullParameter(s) | Error::StringTooLong(s) | Error::InvalidUtf8(s) => {
+                s.to_string()
+            }
+            Error::Other(s) | Error::Panic(s) => s.clone(),
+            Error::InvalidHandle(h) | Error::WrongHandleType(h) => h.to_string(),
+            Error::BufferTooSmall(needed) => needed.to_string(),
+            Error::InteriorNul(offset) => offset.to_string(),
+            Error::LibraryError(_, msg) => msg.clone(),
+            Error::Wrapped { message, .. } => message.clone(),
+            Error::Context { msg, source } => format!("{}: {}", msg, source.details()),
+        }
+    }
+
+    /// Serializes this error as a structured JSON document:
+    /// `{"variant":...,"details":...,"code":...,"chain":[...]}`. `chain` is
+    /// omitted when empty. Use [`Error::last_json`] to additionally include
+    /// the thread-local backtrace, if one was captured.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"variant\":");
+        push_json_string(&mut out, self.variant());
+        out.push_str(",\"details\":");
+        push_json_string(&mut out, &self.details());
+        out.push_str(",\"code\":");
+        out.push_str(&self.code().to_string());
+
+        let chain: Vec<&str> = self.chain().collect();
+        if !chain.is_empty() {
+            out.push_str(",\"chain\":[");
+            for (i, link) in chain.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                push_json_string(&mut out, link);
+            }
+            out.push(']');
+        }
+
+        out.push('}');
+        out
+    }
+
+    /// Serializes the last error set via [`Error::set_last`] as JSON (see
+    /// [`Error::to_json`]), with a `"backtrace"` field appended when
+    /// [`Error::last_backtrace`] captured one. Returns `None` if no error is
+    /// set.
+    #[cfg(not(feature = "no_std"))]
+    pub fn last_json() -> Option<String> {
+        LAST_ERROR.with(|prev| {
+            prev.borrow().as_ref().map(|e| {
+                let mut json = e.to_json();
+                if let Some(backtrace) = Error::last_backtrace() {
+                    json.pop(); // remove trailing '}'
+                    json.push_str(",\"backtrace\":");
+                    push_json_string(&mut json, &backtrace);
+                    json.push('}');
+                }
+                json
+            })
+        })
+    }
+
+    /// Serializes the last error as JSON. See the std version above - never
+    /// has a `"backtrace"` field under `no_std`.
+    #[cfg(feature = "no_std")]
+    pub fn last_json() -> Option<String> {
+        crate::no_std_support::LAST_ERROR.with(|e| e.map(|e| e.to_json()))
+    }
+
     /// Converts a library error using a mapping table
     ///
     /// This method takes an error from an external crate and converts it to a cimpl Error
     /// using a predefined mapping table. The table is typically generated by the
     /// `define_error_codes!` macro.
     ///
+    /// Like [`Error::from_error`], walks `source()` (capped at
+    /// [`MAX_CHAIN_DEPTH`]) and appends the chain to the rendered message, so
+    /// a mapped library error doesn't lose its root cause either.
+    ///
     /// # Example
     ///
     /// ```rust,ignore
@@ -198,13 +877,14 @@ impl Error {
     ///     }
     /// }
     /// ```
-    pub fn from_table<E: std::fmt::Display>(e: E, table: ErrorTable<E>) -> Self {
+    pub fn from_table<E: std::error::Error>(e: E, table: ErrorTable<E>) -> Self {
+        let chain = render_chain(&capture_chain(&e));
         for (matcher, name, code) in table {
             if matcher(&e) {
-                return Error::LibraryError(*code, format!("{}: {}", name, e));
+                return Error::LibraryError(*code, format!("{}: {}{}", name, e, chain));
             }
         }
-        Error::Other(format!("Other: {}", e))
+        Error::Other(format!("Other: {}{}", e, chain))
     }
 
     /// Converts a library error using a mapping function
@@ -213,6 +893,9 @@ impl Error {
     /// that directly maps errors to (code, name) pairs. This is more flexible and
     /// easier to customize than table-based approaches.
     ///
+    /// Like [`Error::from_table`], appends the `source()` chain (capped at
+    /// [`MAX_CHAIN_DEPTH`]) to the rendered message.
+    ///
     /// # Arguments
     ///
     /// * `e` - The external error to convert
@@ -241,18 +924,140 @@ impl Error {
     ///     }
     /// }
     /// ```
-    pub fn from_mapper<E: std::fmt::Display>(e: E, mapper: fn(&E) -> (i32, &'static str)) -> Self {
+    pub fn from_mapper<E: std::error::Error>(e: E, mapper: fn(&E) -> (i32, &'static str)) -> Self {
         let (code, name) = mapper(&e);
-        Error::LibraryError(code, format!("{}: {}", name, e))
+        let chain = render_chain(&capture_chain(&e));
+        Error::LibraryError(code, format!("{}: {}{}", name, e, chain))
     }
 
     /// Sets the last error
+    ///
+    /// If `CIMPL_BACKTRACE=1` is set, also captures a backtrace, available
+    /// afterwards via [`Error::last_backtrace`]. Capture is skipped entirely
+    /// when disabled, so the common failure path pays only one cached env
+    /// lookup. Under the `no_std` feature, backtrace capture is skipped
+    /// unconditionally (see [`Error::last_backtrace`]).
+    #[cfg(not(feature = "no_std"))]
     pub fn set_last(self) {
+        let backtrace = backtrace_capture_enabled()
+            .then(|| std::backtrace::Backtrace::force_capture().to_string());
+        LAST_BACKTRACE.with(|prev| *prev.borrow_mut() = backtrace);
         LAST_ERROR.with(|prev| *prev.borrow_mut() = Some(self));
     }
 
+    /// Sets the last error. See the std version above.
+    #[cfg(feature = "no_std")]
+    pub fn set_last(self) {
+        crate::no_std_support::LAST_ERROR.set(Some(self));
+    }
+
     /// Takes the the last error and clears it
+    #[cfg(not(feature = "no_std"))]
     pub fn take_last() -> Option<Error> {
         LAST_ERROR.with(|prev| prev.borrow_mut().take())
     }
+
+    /// Takes the last error and clears it. See the std version above.
+    #[cfg(feature = "no_std")]
+    pub fn take_last() -> Option<Error> {
+        crate::no_std_support::LAST_ERROR.take()
+    }
+}
+
+/// Appends `s` to `out` as a quoted, escaped JSON string literal.
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Error, Debug)]
+    enum RootError {
+        #[error("disk full")]
+        DiskFull,
+    }
+
+    #[derive(Error, Debug)]
+    enum MiddleError {
+        #[error("write failed")]
+        WriteFailed(#[source] RootError),
+    }
+
+    #[derive(Error, Debug)]
+    enum TopError {
+        #[error("save failed")]
+        SaveFailed(#[source] MiddleError),
+    }
+
+    #[test]
+    fn from_error_preserves_the_full_source_chain() {
+        let err = TopError::SaveFailed(MiddleError::WriteFailed(RootError::DiskFull));
+        let wrapped = Error::from_error(err);
+
+        assert_eq!(
+            wrapped.chain().collect::<Vec<_>>(),
+            vec!["write failed", "disk full"]
+        );
+        assert_eq!(wrapped.root_cause(), Some("disk full"));
+        assert_eq!(
+            wrapped.to_string(),
+            "SaveFailed: save failed: write failed: disk full"
+        );
+        // details() stays the bare top-level message, unchanged, so existing
+        // parsers that split to_string() on the variant prefix still work.
+        assert_eq!(wrapped.details(), "save failed");
+    }
+
+    #[test]
+    fn render_chain_stops_at_max_depth() {
+        let chain: Vec<String> = (0..MAX_CHAIN_DEPTH + 5)
+            .map(|i| format!("cause{i}"))
+            .collect();
+        let rendered = render_chain(&chain);
+        assert_eq!(rendered.matches(": cause").count(), MAX_CHAIN_DEPTH);
+    }
+
+    #[test]
+    fn error_code_registry_includes_builtins_and_custom_entries() {
+        let before = cimpl_error_code_count();
+        assert!(before >= builtin_error_code_entries().len());
+
+        register_error_code_entry(12345, "TestRegistryEntry", "TestRegistryEntry: {0}");
+        assert_eq!(cimpl_error_code_count(), before + 1);
+
+        let last = before;
+        let mut code = 0;
+        let mut name: *mut std::os::raw::c_char = std::ptr::null_mut();
+        let rc = unsafe { cimpl_error_code_at(last, &mut code, &mut name) };
+        assert_eq!(rc, 0);
+        assert_eq!(code, 12345);
+        assert!(!name.is_null());
+        let name_str = unsafe { std::ffi::CStr::from_ptr(name) }
+            .to_string_lossy()
+            .into_owned();
+        assert_eq!(name_str, "TestRegistryEntry");
+        crate::utils::cimple_free(name as *mut std::ffi::c_void);
+
+        assert!(cimpl_error_code_message_at(last + 1000).is_null());
+        let mut out_code = 0;
+        let mut out_name: *mut std::os::raw::c_char = std::ptr::null_mut();
+        assert_eq!(
+            unsafe { cimpl_error_code_at(last + 1000, &mut out_code, &mut out_name) },
+            -1
+        );
+    }
 }
@@ -0,0 +1,217 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! `IntoFfi`: converting Rust return values into their FFI representation
+//!
+//! Every getter in a hand-written wrapper repeats the same boilerplate: convert
+//! the value with `to_c_string`/cast it to the right integer width, and pick a
+//! sentinel to return on error. `IntoFfi` pulls both halves - the conversion and
+//! the sentinel - onto the type itself, so [`ffi_fn!`] can generate the
+//! `extern "C"` shim, run the body under panic-catching, and convert the result
+//! without the function author repeating any of it.
+
+use std::os::raw::c_char;
+
+use crate::error::Error;
+use crate::utils::to_c_string;
+
+/// Converts a Rust value into its FFI-safe representation.
+///
+/// `Value` is the type the `extern "C"` function actually returns (e.g.
+/// `*mut c_char` for `String`). `ffi_default()` is the sentinel returned when
+/// the call produced no value - an error was set, or a panic was caught.
+pub trait IntoFfi {
+    /// The FFI-safe type this converts into.
+    type Value;
+
+    /// The sentinel returned on error or panic instead of a real value.
+    fn ffi_default() -> Self::Value;
+
+    /// Converts `self` into its FFI representation.
+    fn into_ffi(self) -> Self::Value;
+}
+
+impl IntoFfi for String {
+    type Value = *mut c_char;
+
+    fn ffi_default() -> Self::Value {
+        std::ptr::null_mut()
+    }
+
+    fn into_ffi(self) -> Self::Value {
+        to_c_string(self)
+    }
+}
+
+impl IntoFfi for bool {
+    type Value = bool;
+
+    fn ffi_default() -> Self::Value {
+        false
+    }
+
+    fn into_ffi(self) -> Self::Value {
+        self
+    }
+}
+
+macro_rules! impl_into_ffi_int {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl IntoFfi for $t {
+                type Value = $t;
+
+                fn ffi_default() -> Self::Value {
+                    0 as $t
+                }
+
+                fn into_ffi(self) -> Self::Value {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_into_ffi_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+impl<T: IntoFfi> IntoFfi for Option<T> {
+    type Value = T::Value;
+
+    fn ffi_default() -> Self::Value {
+        T::ffi_default()
+    }
+
+    fn into_ffi(self) -> Self::Value {
+        match self {
+            Some(value) => value.into_ffi(),
+            None => T::ffi_default(),
+        }
+    }
+}
+
+impl<T: IntoFfi> IntoFfi for Result<T, Error> {
+    type Value = T::Value;
+
+    fn ffi_default() -> Self::Value {
+        T::ffi_default()
+    }
+
+    fn into_ffi(self) -> Self::Value {
+        match self {
+            Ok(value) => value.into_ffi(),
+            Err(err) => {
+                err.set_last();
+                T::ffi_default()
+            }
+        }
+    }
+}
+
+/// Generates an `extern "C"` function whose body returns a plain Rust value
+/// instead of hand-rolling pointer/sentinel conversions.
+///
+/// The body runs inside `catch_unwind` (same as [`call_with_result!`]); on a
+/// caught panic, `Error::Panic` is set as the last error and
+/// `<$ret as IntoFfi>::ffi_default()` is returned. On a normal return, the
+/// value is converted through [`IntoFfi::into_ffi`].
+///
+/// ```rust,ignore
+/// ffi_fn! {
+///     fn mystring_get_value(ptr: *mut MyString) -> String {
+///         let s = deref_or_return!(ptr, MyString, String::new());
+///         s.value.clone()
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! ffi_fn {
+    (fn $name:ident($($arg:ident : $argty:ty),* $(,)?) -> $ret:ty $body:block) => {
+        #[no_mangle]
+        pub extern "C" fn $name($($arg: $argty),*) -> <$ret as $crate::IntoFfi>::Value {
+            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| -> $ret { $body })) {
+                Ok(value) => $crate::IntoFfi::into_ffi(value),
+                Err(payload) => {
+                    let msg = $crate::macros::panic_message(&payload);
+                    $crate::Error::Panic(msg).set_last();
+                    <$ret as $crate::IntoFfi>::ffi_default()
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ffi_fn! {
+        fn test_ffi_fn_returns_string() -> String {
+            "hello".to_string()
+        }
+    }
+
+    ffi_fn! {
+        fn test_ffi_fn_panics() -> String {
+            panic!("boom")
+        }
+    }
+
+    ffi_fn! {
+        fn test_ffi_fn_result_ok() -> Result<String, Error> {
+            Ok("ok".to_string())
+        }
+    }
+
+    ffi_fn! {
+        fn test_ffi_fn_result_err() -> Result<String, Error> {
+            Err(Error::NullParameter("x"))
+        }
+    }
+
+    /// Reads a `*mut c_char` produced by `into_ffi()` and frees it through
+    /// the real `cimple_free` path, matching how a caller actually would.
+    unsafe fn take_c_string(ptr: *mut c_char) -> String {
+        let s = std::ffi::CStr::from_ptr(ptr).to_str().unwrap().to_string();
+        crate::utils::cimple_free(ptr as *mut std::ffi::c_void);
+        s
+    }
+
+    #[test]
+    fn ffi_fn_converts_a_plain_return_value() {
+        let ptr = test_ffi_fn_returns_string();
+        assert!(!ptr.is_null());
+        assert_eq!(unsafe { take_c_string(ptr) }, "hello");
+    }
+
+    #[test]
+    fn ffi_fn_catches_a_panic_and_returns_the_sentinel() {
+        let ptr = test_ffi_fn_panics();
+        assert!(ptr.is_null());
+        assert!(matches!(Error::take_last(), Some(Error::Panic(_))));
+    }
+
+    #[test]
+    fn ffi_fn_converts_ok_through_into_ffi() {
+        let ptr = test_ffi_fn_result_ok();
+        assert!(!ptr.is_null());
+        assert_eq!(unsafe { take_c_string(ptr) }, "ok");
+    }
+
+    #[test]
+    fn ffi_fn_converts_err_to_the_sentinel_and_sets_last_error() {
+        let ptr = test_ffi_fn_result_err();
+        assert!(ptr.is_null());
+        assert!(matches!(Error::take_last(), Some(Error::NullParameter(_))));
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Generational Handle Map
+//!
+//! An opt-in alternative to `box_tracked!`/`validate_pointer` for FFI objects.
+//! Instead of handing C a raw pointer that can collide with a freshly allocated
+//! object at the same address, a `HandleMap<T>` hands out an opaque `u64` handle
+//! that packs a slot index, a generation counter, and a per-map id. A stale or
+//! forged handle is rejected even if the slot has since been reused.
+//!
+//! Use this when the strength of generational validation matters more than the
+//! convenience of a raw pointer; otherwise `box_tracked!` remains the default.
+
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::error::Error;
+use crate::slab::Slab;
+
+/// Recovers a poisoned lock instead of propagating the panic, mirroring
+/// `PointerRegistry`'s `lock()` helper in `utils.rs`. `with_mut` runs a
+/// caller-supplied closure while holding the write lock, so a panic in that
+/// closure must not permanently poison every later call against this map -
+/// the slot table itself is still perfectly consistent after an unwind,
+/// since no partial mutation of it happens around the closure call.
+fn read_lock<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Write-lock counterpart to [`read_lock`].
+fn write_lock<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Packs/unpacks the 64-bit opaque handle.
+///
+/// Layout (low to high bits): 32 bits slot index, 16 bits generation, 16 bits map id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PackedHandle {
+    index: u32,
+    generation: u16,
+    map_id: u16,
+}
+
+impl PackedHandle {
+    fn pack(self) -> u64 {
+        (self.index as u64) | ((self.generation as u64) << 32) | ((self.map_id as u64) << 48)
+    }
+
+    fn unpack(handle: u64) -> Self {
+        Self {
+            index: (handle & 0xFFFF_FFFF) as u32,
+            generation: ((handle >> 32) & 0xFFFF) as u16,
+            map_id: ((handle >> 48) & 0xFFFF) as u16,
+        }
+    }
+}
+
+static NEXT_MAP_ID: AtomicU16 = AtomicU16::new(1);
+
+/// Picks a per-map id that's hard to guess, rather than a predictable
+/// sequence - so a forged handle can't just increment `map_id` until it
+/// matches some live map. Seeded from `RandomState`'s OS-provided key (the
+/// same source `HashMap`'s DoS-resistant hashing uses), not a dependency on
+/// an external RNG crate. Never returns `0`, which every real handle's
+/// nonzero `map_id` bits reserve as a safe "invalid handle" sentinel.
+fn random_map_id() -> u16 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u16(NEXT_MAP_ID.fetch_add(1, Ordering::Relaxed));
+    match hasher.finish() as u16 {
+        0 => 1,
+        id => id,
+    }
+}
+
+/// A thread-safe, generational slot map that hands out opaque `u64` handles.
+/// The slot+generation+free-list bookkeeping itself lives in [`crate::slab::Slab`],
+/// shared with `PointerRegistry`'s handle table in `utils.rs`; this type adds
+/// the per-map id and the `RwLock` this crate uses outside `no_std`.
+///
+/// `insert` returns a handle; `with`/`with_mut` validate the handle's
+/// generation and map id before running the caller's closure, and `remove`
+/// bumps the slot's generation so every previously issued handle for that
+/// slot is permanently rejected (defeats ABA / use-after-free via recycled
+/// slots).
+pub struct HandleMap<T> {
+    map_id: u16,
+    slots: RwLock<Slab<T, u16>>,
+}
+
+impl<T> Default for HandleMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> HandleMap<T> {
+    /// Creates a new, empty handle map with a fresh per-map id.
+    pub fn new() -> Self {
+        Self {
+            map_id: random_map_id(),
+            slots: RwLock::new(Slab::default()),
+        }
+    }
+
+    /// Unpacks `handle` and checks its map id, without yet touching the slab.
+    fn unpack_for_self(&self, handle: u64) -> Result<PackedHandle, Error> {
+        let packed = PackedHandle::unpack(handle);
+        if packed.map_id != self.map_id {
+            return Err(Error::WrongHandleType(handle));
+        }
+        Ok(packed)
+    }
+
+    /// Inserts a value and returns an opaque handle for it.
+    pub fn insert(&self, value: T) -> u64 {
+        let (index, generation) = write_lock(&self.slots).insert(value);
+        PackedHandle {
+            index: index as u32,
+            generation,
+            map_id: self.map_id,
+        }
+        .pack()
+    }
+
+    /// Runs `f` with a shared reference to the value behind `handle`.
+    pub fn with<R>(&self, handle: u64, f: impl FnOnce(&T) -> R) -> Result<R, Error> {
+        let packed = self.unpack_for_self(handle)?;
+        let slots = read_lock(&self.slots);
+        match slots.get(packed.index as usize, packed.generation) {
+            Some(value) => Ok(f(value)),
+            None => Err(Error::InvalidHandle(handle)),
+        }
+    }
+
+    /// Runs `f` with an exclusive reference to the value behind `handle`.
+    pub fn with_mut<R>(&self, handle: u64, f: impl FnOnce(&mut T) -> R) -> Result<R, Error> {
+        let packed = self.unpack_for_self(handle)?;
+        let mut slots = write_lock(&self.slots);
+        match slots.get_mut(packed.index as usize, packed.generation) {
+            Some(value) => Ok(f(value)),
+            None => Err(Error::InvalidHandle(handle)),
+        }
+    }
+
+    /// Removes the value behind `handle`, bumping the slot's generation so every
+    /// previously issued copy of `handle` is rejected from now on.
+    pub fn remove(&self, handle: u64) -> Result<T, Error> {
+        let packed = self.unpack_for_self(handle)?;
+        let mut slots = write_lock(&self.slots);
+        let index = packed.index as usize;
+        if slots.get(index, packed.generation).is_none() {
+            return Err(Error::InvalidHandle(handle));
+        }
+        let value = slots.take(index);
+        slots.retire(index);
+        Ok(value)
+    }
+}
+
+/// Dereference a handle, running the body and returning early with `$err_val` on error.
+#[macro_export]
+macro_rules! deref_handle_or_return {
+    ($map:expr, $handle:expr, $err_val:expr, |$value:ident| $body:expr) => {{
+        match $map.with($handle, |$value| $body) {
+            Ok(result) => result,
+            Err(e) => {
+                e.set_last();
+                return $err_val;
+            }
+        }
+    }};
+}
+
+/// Dereference a handle, returning NULL on error.
+#[macro_export]
+macro_rules! deref_handle_or_return_null {
+    ($map:expr, $handle:expr, |$value:ident| $body:expr) => {
+        $crate::deref_handle_or_return!($map, $handle, std::ptr::null_mut(), |$value| $body)
+    };
+}
+
+/// Dereference a handle, returning -1 on error.
+#[macro_export]
+macro_rules! deref_handle_or_return_neg {
+    ($map:expr, $handle:expr, |$value:ident| $body:expr) => {
+        $crate::deref_handle_or_return!($map, $handle, -1, |$value| $body)
+    };
+}
+
+/// Dereference a handle, returning false on error.
+#[macro_export]
+macro_rules! deref_handle_or_return_false {
+    ($map:expr, $handle:expr, |$value:ident| $body:expr) => {
+        $crate::deref_handle_or_return!($map, $handle, false, |$value| $body)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_read() {
+        let map: HandleMap<String> = HandleMap::new();
+        let h = map.insert("hello".to_string());
+        assert_eq!(map.with(h, |s| s.clone()).unwrap(), "hello");
+    }
+
+    #[test]
+    fn stale_handle_after_remove_is_rejected() {
+        let map: HandleMap<i32> = HandleMap::new();
+        let h = map.insert(42);
+        assert_eq!(map.remove(h).unwrap(), 42);
+        assert!(matches!(map.with(h, |v| *v), Err(Error::InvalidHandle(_))));
+    }
+
+    #[test]
+    fn reused_slot_gets_new_generation() {
+        let map: HandleMap<i32> = HandleMap::new();
+        let h1 = map.insert(1);
+        map.remove(h1).unwrap();
+        let h2 = map.insert(2);
+        // Same slot index, different generation, so h1 must stay invalid.
+        assert!(map.with(h1, |v| *v).is_err());
+        assert_eq!(map.with(h2, |v| *v).unwrap(), 2);
+    }
+
+    #[test]
+    fn handle_from_a_different_map_is_rejected() {
+        let map_a: HandleMap<i32> = HandleMap::new();
+        let map_b: HandleMap<i32> = HandleMap::new();
+        let h = map_a.insert(7);
+        assert!(matches!(
+            map_b.with(h, |v| *v),
+            Err(Error::WrongHandleType(_))
+        ));
+    }
+
+    #[test]
+    fn random_map_id_is_never_zero() {
+        for _ in 0..256 {
+            assert_ne!(random_map_id(), 0);
+        }
+    }
+
+    #[test]
+    fn survives_a_panic_inside_with_mut() {
+        let map: HandleMap<i32> = HandleMap::new();
+        let h = map.insert(1);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            map.with_mut(h, |_| panic!("boom"))
+        }));
+        assert!(result.is_err());
+
+        // The panic poisoned `slots`; every later call must still work
+        // instead of panicking again on a poisoned lock.
+        assert_eq!(map.with(h, |v| *v).unwrap(), 1);
+        let h2 = map.insert(2);
+        assert_eq!(map.with(h2, |v| *v).unwrap(), 2);
+    }
+}
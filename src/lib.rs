@@ -141,16 +141,162 @@
 //! - [`deref_or_return!`] - Pointer validation and dereferencing (immutable)
 //! - [`deref_mut_or_return!`] - Pointer validation and dereferencing (mutable)
 //!
+//! ### Generational Handles (opt-in alternative to raw pointers)
+//!
+//! - [`handle::HandleMap`] - Slab of generation-tagged slots, returns opaque `u64` handles,
+//!   for libraries that want to hand-roll a single-type handle API. Each map's
+//!   `map_id` bits are drawn from an OS-seeded `RandomState` rather than a
+//!   predictable sequence, so a handle forged by guessing/incrementing can't
+//!   walk its way onto a live map
+//! - [`deref_handle_or_return!`] / `deref_handle_or_return_null!` / `_neg!` / `_false!` -
+//!   Look up a handle, rejecting stale or forged ones with `Error::InvalidHandle`
+//! - [`utils::track_box_as_handle()`] / [`utils::track_arc_as_handle()`] - Same generational
+//!   protection, but integrated into the `cimple_free()` registry: a raw pointer address
+//!   that's been freed and reused for a new, same-typed object would otherwise still
+//!   `validate()` successfully against it; these return an opaque `u64` instead, freed
+//!   with [`utils::cimple_free_handle()`]
+//!
+//! ### Panic Safety
+//!
+//! A Rust panic unwinding across an `extern "C"` boundary is undefined behavior.
+//! Wrap FFI function bodies in [`call_with_result!`] / [`call_with_output!`]
+//! (or [`catch_ffi!`], an identically-behaved alias for call sites that read
+//! better as "catch any panic here") to catch a panic, record it as
+//! `Error::Panic`, and return the caller-supplied sentinel instead of
+//! unwinding into C. [`macros::install_panic_hook()`] is a
+//! one-time companion call for hosts that also want the default
+//! `thread '...' panicked at ...` stderr print suppressed, so reporting goes
+//! exclusively through the cimpl error channel. [`utils::cimple_free()`] itself follows
+//! this rule - a tracked cleanup function is arbitrary caller-supplied Drop
+//! code, so it's run under the same panic-catching - and the registry's
+//! internal locks recover from poisoning instead of propagating it, so one
+//! panicked call never deadlocks (or blanket-fails) every call after it.
+//!
+//! ### Per-Call Error Reporting (alternative to the thread-local last error)
+//!
+//! - [`ExternError`] (aka [`error::CExternError`]) - `#[repr(C)]` out-parameter:
+//!   `code` + an allocated `message`
+//! - [`ok_or_return_out_err!`] (aka [`ok_or_return_err!`]) - Like
+//!   [`ok_or_return!`], but also fills an `ExternError`
+//! - [`with_extern_error!`] - Combines panic-catching with `ExternError` reporting
+//! - [`extern_error_free()`] (aka [`error::cimpl_error_free()`]) - Releases the
+//!   `message` allocation of a populated `ExternError`
+//!
+//! ### Error Diagnostics
+//!
+//! - [`Error::from_error()`] (also [`Error::from_table()`] /
+//!   [`Error::from_mapper()`]) - Convert an external error, capturing its
+//!   `source()` chain (capped at [`error::MAX_CHAIN_DEPTH`]) and folding it
+//!   into the rendered message as `"variant: message: cause1: cause2"`, so
+//!   the root cause survives crossing the FFI boundary as plain text
+//! - [`Error::chain()`] / [`Error::root_cause()`] - Walk the captured cause chain
+//! - [`context!`] / `Error::Context` - Annotates a fallible call with what it was
+//!   doing (`"while parsing manifest: invalid utf-8"`), without changing the
+//!   underlying error's code - see [`Error::code_as_i32()`]
+//! - [`Error::last_backtrace()`] - Formatted frames, captured when `CIMPL_BACKTRACE=1`.
+//!   This already covers "capture a backtrace at set_last time, gated so the
+//!   common case pays nothing" - bindings that want it should read this
+//!   instead of rolling a second, competing capture path
+//! - [`Error::code()`] / [`register_error_codes!`] (aka [`define_error_codes!`]) -
+//!   Stable `u32` codes for wrapped errors, so bindings can switch on an
+//!   integer instead of parsing the message
+//! - [`error::cimpl_error_code_count()`] / [`error::cimpl_error_code_at()`] /
+//!   [`error::cimpl_error_code_message_at()`] - Enumerate every registered
+//!   `(code, name, message_template)` at load time - the built-in cimpl codes
+//!   plus anything added via `register_error_codes!`/`define_error_codes!` -
+//!   so a binding generator can synthesize one exception class per code
+//!   without parsing a generated header
+//! - [`Error::to_json()`] / [`Error::last_json()`] - Structured JSON instead of
+//!   the flat `"Variant: details"` string, for bindings that deserialize errors
+//!
+//! ### Declarative Return Conversion
+//!
+//! - [`IntoFfi`] - Converts a Rust value (`String`, `bool`, integers, `Option<T>`,
+//!   `Result<T, Error>`) into its FFI type, with a sentinel default for errors
+//! - [`ffi_fn!`] - Generates an `extern "C"` shim: runs the body under panic-catching,
+//!   then converts the result through `IntoFfi`, removing per-function sentinel handling
+//!
 //! ### String Conversion
 //!
-//! - [`cstr_or_return!`] - C string to Rust with UTF-8 validation and bounds checking
-//! - [`to_c_string()`] - Rust String to tracked C string
+//! - [`cstr_or_return!`] - C string to owned Rust `String`, with bounds checking
+//! - [`macros::FfiStr`] / [`cstr_ref_or_return!`] - Borrowed `&str` with no allocation,
+//!   for wrappers that only need a `&str` for the duration of the call
+//! - [`cstr_borrow_or_return!`] - Like `cstr_ref_or_return!`, but yields the
+//!   borrowed `&str` directly instead of an `FfiStr`, rejecting non-UTF-8 with
+//!   `Error::InvalidUtf8` instead of lossily replacing it
+//! - [`to_c_string()`] - Rust String to tracked C string, rejecting interior
+//!   NUL bytes with `Error::InteriorNul` rather than silently truncating
+//! - [`to_c_string_lossy()`] - Same, but replaces interior NULs instead of failing
 //! - [`option_to_c_string!`] - Option<String> to C string (NULL if None)
+//! - [`cstr_lossy_or_return!`] / [`cstr_lossy_or_return_null!`] - Same
+//!   loss-tolerant conversion `cstr_or_return!` already does, under a name
+//!   that documents it as deliberate for text-processing call sites
+//!
+//! ### Wide-String Conversion (Windows `*const u16` / UTF-16)
+//!
+//! - [`wstr::Wtf8Buf`] - Lossless WTF-8 buffer for ill-formed UTF-16 (unpaired
+//!   surrogates), via [`wstr::Wtf8Buf::from_wide()`]/[`wstr::Wtf8Buf::to_wide()`]
+//! - [`wstr_or_return!`] / [`wstr_or_return_null!`] - `*const u16` to `Wtf8Buf`,
+//!   with bounds checking, mirroring [`cstr_or_return!`] for wide strings
+//! - [`to_c_wstring()`] - `Wtf8Buf` to tracked, NUL-terminated `*mut u16`
 //!
 //! ### Byte Array Handling
 //!
 //! - [`bytes_or_return!`] - Validate and convert C byte arrays
 //! - [`to_c_bytes()`] - Rust Vec<u8> to tracked C byte array
+//! - [`bytestring::CByteString`] - Opaque, tracked byte string with no UTF-8
+//!   requirement, for payloads that may not be valid text at all; render it
+//!   for logs with [`cbytestring_debug()`] without losing the original bytes
+//!
+//! ### Pluggable Host Allocator
+//!
+//! - [`cimple_set_allocator()`] - Registers a host `alloc`/`realloc`/`free`
+//!   ([`CimplAllocator`]); once set, [`to_c_string()`]/[`to_c_bytes()`]
+//!   allocate through it (and their `free_c_*` counterparts free through it),
+//!   so a buffer crossing the FFI boundary is always allocated and freed by
+//!   the same C runtime - required on Windows, or anywhere else the host app
+//!   and this shared library don't share a heap
+//!
+//! ### Caller-Provided Buffers (zero-allocation alternative)
+//!
+//! - [`write_cstr_to_buf()`] / [`write_bytes_to_buf()`] - Encode directly into
+//!   a caller-owned buffer instead of allocating a tracked pointer. Returns
+//!   `ErrorCode::BufferTooSmall` (without writing past `cap`) when the buffer
+//!   is short, and always reports the required size via `out_len` so callers
+//!   can query it first and retry
+//!
+//! ### Owned Container Types (replace pointer + out-length pairs)
+//!
+//! - [`CimplBytes`] / [`to_cimpl_bytes()`] - Owned `{ptr, len}` byte buffer
+//! - [`CimplVec<T>`] / [`to_cimpl_vec()`] - Owned `{ptr, len, cap}` array of `T`,
+//!   for returning a list of handles or primitives in one call
+//! - [`CimplOption<T>`] - `{is_some, value}` for nullable scalars with no
+//!   spare NULL sentinel
+//!
+//! `CimplBytes`/`CimplVec` pointers are tracked in the same registry as
+//! `box_tracked!`, so [`cimpl_free()`] recognizes and releases them.
+//!
+//! ### JSON Serialization (for aggregate values)
+//!
+//! - [`json_tracked!`] - Serializes any `serde::Serialize` value to a tracked
+//!   JSON C string
+//! - [`serialized_or_return_null!`] - Like `json_tracked!`, but returns NULL
+//!   instead of panicking if serialization fails
+//! - [`from_json_or_return_null!`] - Reads a bounded C string and
+//!   deserializes it into a `serde::Deserialize` type, routing parse errors
+//!   through the last-error channel
+//!
+//! These require the calling crate to depend on `serde`/`serde_json`
+//! itself - cimpl doesn't take on that dependency, matching how `ok_or_return!`
+//! already lets external error types be named at the call site.
+//!
+//! ### Tagged-Union Result (self-contained, no side channel)
+//!
+//! - [`CimplResult`] - `#[repr(C)]` success/error union: `tag` selects
+//!   `ok_ptr` or `code`/`err_msg`, so the value is fully self-contained
+//! - [`ok_or_return_result!`] - Packages a `Result<T, E>` into a `CimplResult`,
+//!   boxing and tracking the success value like `box_tracked!`
+//! - [`cimpl_result_free()`] - Releases the `err_msg` allocation, if any
 //!
 //! ### Result Handling
 //!
@@ -164,6 +310,15 @@
 //! - [`some_or_return!`] - Option unwrapping with custom errors
 //! - [`some_or_return_other_null!`] - Option with Error::other message, return NULL
 //!
+//! ### C++ Wrapper Generation
+//!
+//! - [`cpp_codegen::generate_cpp_wrappers()`] - A second, optional `build.rs`
+//!   step run after `cbindgen`, emitting a companion `.hpp` with one RAII
+//!   wrapper class per [`cpp_codegen::OpaqueTypeSpec`]: constructor calls
+//!   `create_fn`, destructor calls `free_fn`, `char*`-returning methods copy
+//!   into `std::string` and free the buffer, and any failure throws a
+//!   shared `CimplException` carrying the `ErrorCode` and last-error message
+//!
 //! ## Memory Management
 //!
 //! All pointers allocated via `box_tracked!`, `arc_tracked!`, or the tracking functions are
@@ -182,6 +337,18 @@
 //! - **Double-free protection**: Registry prevents freeing the same pointer twice
 //! - **Leak detection**: Unfreed pointers reported at program exit
 //!
+//! Pointers tracked via [`track_arc()`]/[`track_arc_mutex()`] also support
+//! sharing: [`cimple_clone()`] mints a second, independently-freeable handle
+//! (the backing value is only dropped once every handle has been freed), and
+//! [`cimple_downgrade()`]/[`cimple_upgrade()`] let C hold a non-owning `Weak`
+//! reference that can later be promoted back to an owning one, or rejected
+//! with NULL if the value is already gone.
+//!
+//! Leak detection isn't only a shutdown-time `eprintln!`: [`utils::get_registry()`]`.snapshot()`
+//! and [`utils::get_allocations()`]`.snapshot()` return the same counts, grouped by type,
+//! as plain data, and [`utils::assert_no_leaks()`] wraps both into a single `Result` a
+//! `#[test]` can assert on directly instead of scraping stderr.
+//!
 //! ## AI-Friendly Design
 //!
 //! cimpl is designed to enable AI code generation. See [`AI_WORKFLOW.md`] in the repository for:
@@ -222,22 +389,56 @@
 //!
 //! [`AI_WORKFLOW.md`]: https://github.com/gpeacock/cimpl/blob/main/AI_WORKFLOW.md
 //! [`PHILOSOPHY.md`]: https://github.com/gpeacock/cimpl/blob/main/PHILOSOPHY.md
+//!
+//! ## `no_std` Support (in progress)
+//!
+//! The `no_std` feature swaps the two seams that currently hard-depend on
+//! std - the thread-local last-error slot, and the `std::sync::Mutex`-backed
+//! tracking tables in [`utils`] - for spin-lock-based equivalents (the
+//! [`no_std_support::ErrorSink`] trait for the error slot, and
+//! [`no_std_support::SpinMutex`] for the tables), so enclave-style targets with no OS
+//! mutex or thread-locals can still use the error-reporting macros
+//! unchanged. See [`no_std_support`].
+//!
+//! This does not yet mark the crate itself `#![no_std]` - a handful of other
+//! call sites (`CString` allocation in [`utils::to_c_string`], the
+//! leak-detection `eprintln!`s, `CIMPL_BACKTRACE` capture) still assume std
+//! and need their own `alloc`-only equivalents before that's possible.
 
 // Declare foundational modules first
+pub mod bytestring;
+pub mod containers;
+pub mod cpp_codegen;
 pub mod error;
+pub mod ffi;
+pub mod handle;
+#[cfg(feature = "no_std")]
+pub mod no_std_support;
+pub(crate) mod slab;
 pub mod utils;
+pub mod wstr;
 
 // Then macros that depend on them
 #[macro_use]
 pub mod macros;
 
 // Re-export main types and functions for convenience
-pub use error::{Error, Result};
+pub use bytestring::{
+    cbytestring_bytes, cbytestring_debug, cbytestring_len, cbytestring_new, CByteString,
+};
+pub use containers::{to_cimpl_bytes, to_cimpl_vec, CimplBytes, CimplOption, CimplVec};
+pub use error::{cimpl_result_free, extern_error_free, CimplResult, Error, ExternError, Result};
 // Convenience alias to avoid name conflicts
 pub use error::Error as CimplError;
+pub use ffi::IntoFfi;
+pub use handle::HandleMap;
+pub use wstr::{from_c_wstring, to_c_wstring, Wtf8Buf};
 pub use utils::{
-    cimpl_free, safe_slice_from_raw_parts, to_c_bytes, to_c_string, track_arc, track_arc_mutex,
-    track_box,
+    assert_no_leaks, cimpl_free, cimple_clone, cimple_downgrade, cimple_free_handle,
+    cimple_set_allocator, cimple_upgrade, leak_report, safe_slice_from_raw_parts, to_c_bytes,
+    to_c_string, to_c_string_lossy, track_arc, track_arc_as_handle, track_arc_mutex, track_box,
+    track_box_as_handle, write_bytes_to_buf, write_cstr_to_buf, AllocationSnapshot,
+    AllocationType, CimplAllocator, TrackedEntrySnapshot,
 };
 
 // Re-export internal utilities (for macro use only - not part of public API)
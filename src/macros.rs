@@ -252,7 +252,7 @@ pub const MAX_CSTRING_LEN: usize = 65536;
 macro_rules! ptr_or_return {
     ($ptr:expr, $err_val:expr) => {
         if $ptr.is_null() {
-            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr).to_string()));
+            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr)));
             return $err_val;
         }
     };
@@ -266,7 +266,7 @@ macro_rules! cstr_or_return {
     ($ptr:expr, $err_val:expr) => {{
         let ptr = $ptr;
         if ptr.is_null() {
-            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr).to_string()));
+            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr)));
             return $err_val;
         } else {
             // SAFETY: We create a bounded slice up to MAX_CSTRING_LEN.
@@ -278,9 +278,7 @@ macro_rules! cstr_or_return {
             match std::ffi::CStr::from_bytes_until_nul(bytes) {
                 Ok(cstr) => cstr.to_string_lossy().into_owned(),
                 Err(_) => {
-                    $crate::Error::set_last($crate::Error::StringTooLong(
-                        stringify!($ptr).to_string(),
-                    ));
+                    $crate::Error::set_last($crate::Error::StringTooLong(stringify!($ptr)));
                     return $err_val;
                 }
             }
@@ -296,7 +294,7 @@ macro_rules! cstr_or_return_with_limit {
         let ptr = $ptr;
         let max_len = $max_len;
         if ptr.is_null() {
-            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr).to_string()));
+            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr)));
             return $err_val;
         } else {
             // SAFETY: We create a bounded slice up to max_len.
@@ -306,9 +304,119 @@ macro_rules! cstr_or_return_with_limit {
             match std::ffi::CStr::from_bytes_until_nul(bytes) {
                 Ok(cstr) => cstr.to_string_lossy().into_owned(),
                 Err(_) => {
-                    $crate::Error::set_last($crate::Error::StringTooLong(
-                        stringify!($ptr).to_string(),
-                    ));
+                    $crate::Error::set_last($crate::Error::StringTooLong(stringify!($ptr)));
+                    return $err_val;
+                }
+            }
+        }
+    }};
+}
+
+/// A borrowed, validated C string, tied to the lifetime of the call.
+///
+/// Unlike `cstr_or_return!`, which eagerly allocates an owned `String` via
+/// `to_string_lossy().into_owned()`, `FfiStr` only checks that the pointer is
+/// non-null and the string is within `MAX_CSTRING_LEN` bytes; the UTF-8 check
+/// (and the only copy-free way to get a `&str` out) happens lazily in
+/// [`FfiStr::as_str`]. Prefer this for wrappers that forward straight to a
+/// `&str`-taking method, to avoid paying for an allocation the method never
+/// needed. Code that needs to keep the string past the call (or mutate it)
+/// should still use `cstr_or_return!`.
+pub struct FfiStr<'a>(&'a std::ffi::CStr);
+
+impl<'a> FfiStr<'a> {
+    /// Validates the borrowed bytes as UTF-8 and returns them as `&str`.
+    pub fn as_str(&self) -> Result<&'a str, crate::Error> {
+        self.0
+            .to_str()
+            .map_err(|_| crate::Error::InvalidUtf8("invalid UTF-8 in C string"))
+    }
+}
+
+/// Borrow a C string as `FfiStr` with a bounded length check, or early-return
+/// with an error value. Does not allocate; UTF-8 is validated lazily by
+/// `FfiStr::as_str()`.
+#[macro_export]
+macro_rules! cstr_ref_or_return {
+    ($ptr:expr, $err_val:expr) => {{
+        let ptr = $ptr;
+        if ptr.is_null() {
+            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr)));
+            return $err_val;
+        } else {
+            // SAFETY: We create a bounded slice up to MAX_CSTRING_LEN.
+            // Caller must ensure ptr is valid for reading and points to a
+            // null-terminated string within MAX_CSTRING_LEN bytes, live for
+            // at least as long as the returned FfiStr is used.
+            let bytes = unsafe {
+                std::slice::from_raw_parts(ptr as *const u8, $crate::macros::MAX_CSTRING_LEN)
+            };
+            match std::ffi::CStr::from_bytes_until_nul(bytes) {
+                Ok(cstr) => $crate::macros::FfiStr(cstr),
+                Err(_) => {
+                    $crate::Error::set_last($crate::Error::StringTooLong(stringify!($ptr)));
+                    return $err_val;
+                }
+            }
+        }
+    }};
+}
+
+/// If the expression is null or too long, set the last error and return std::ptr::null_mut().
+#[macro_export]
+macro_rules! cstr_ref_or_return_null {
+    ($ptr:expr) => {
+        $crate::cstr_ref_or_return!($ptr, std::ptr::null_mut())
+    };
+}
+
+/// If the expression is null or too long, set the last error and return -1.
+#[macro_export]
+macro_rules! cstr_ref_or_return_int {
+    ($ptr:expr) => {
+        $crate::cstr_ref_or_return!($ptr, -1)
+    };
+}
+
+/// Borrow a C string directly as `&str`, with no allocation, or early-return
+/// with an error value.
+///
+/// Like `cstr_ref_or_return!`, this performs the same bounded
+/// `from_bytes_until_nul` scan and yields a value borrowed straight from the
+/// caller's buffer rather than an owned `String` - but it validates UTF-8
+/// eagerly and returns the `&str` itself instead of an `FfiStr` the caller
+/// must call `.as_str()` on, for call sites that always need the string and
+/// have no use for the lazy-validation indirection. Returns
+/// `Error::InvalidUtf8` (not the lossy replacement `cstr_or_return!` would
+/// perform) if the bytes aren't valid UTF-8.
+///
+/// The borrow is only valid for the duration of the call - the pointer must
+/// outlive it, exactly as with `cstr_ref_or_return!`/`FfiStr`.
+#[macro_export]
+macro_rules! cstr_borrow_or_return {
+    ($ptr:expr, $err_val:expr) => {{
+        let ptr = $ptr;
+        if ptr.is_null() {
+            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr)));
+            return $err_val;
+        } else {
+            // SAFETY: We create a bounded slice up to MAX_CSTRING_LEN.
+            // Caller must ensure ptr is valid for reading and points to a
+            // null-terminated string within MAX_CSTRING_LEN bytes, live for
+            // at least as long as the returned &str is used.
+            let bytes = unsafe {
+                std::slice::from_raw_parts(ptr as *const u8, $crate::macros::MAX_CSTRING_LEN)
+            };
+            match std::ffi::CStr::from_bytes_until_nul(bytes) {
+                Ok(cstr) => match cstr.to_str() {
+                    Ok(s) => s,
+                    Err(_) => {
+                        $crate::Error::set_last($crate::Error::InvalidUtf8(stringify!($ptr)));
+                        return $err_val;
+                    }
+                },
+                Err(_) => {
+                    $crate::Error::set_last($crate::Error::StringTooLong(stringify!($ptr)));
                     return $err_val;
                 }
             }
@@ -355,6 +463,257 @@ macro_rules! ok_or_return {
     };
 }
 
+/// Annotates a fallible expression's error with context describing the
+/// operation that was in progress, wrapping it in `Error::Context` while
+/// still yielding a plain `Result<T, Error>` - so `?` keeps working at the
+/// call site exactly as it does for a bare `Result<T, Error>`.
+///
+/// ```rust,ignore
+/// fn load_manifest(bytes: &[u8]) -> Result<Manifest, cimpl::Error> {
+///     let manifest = context!(parse_manifest(bytes), "while parsing manifest")?;
+///     Ok(manifest)
+/// }
+/// ```
+///
+/// `last_message()` on the resulting error renders the full chain, e.g.
+/// `"while parsing manifest: invalid utf-8"`, while `code_as_i32()` still
+/// reports the same code as the unwrapped error - context never changes how
+/// a binding switches on the error.
+#[macro_export]
+macro_rules! context {
+    ($result:expr, $msg:expr) => {
+        $result.map_err(|e| $crate::Error::Context {
+            msg: $msg.to_string(),
+            source: Box::new(e.into()),
+        })
+    };
+}
+
+// ============================================================================
+// Panic-Catching Macros
+// ============================================================================
+//
+// None of the macros above guard against a Rust panic unwinding across the
+// `extern "C"` boundary, which is undefined behavior. These wrap the call body
+// in `std::panic::catch_unwind` and convert a caught panic into a normal
+// `Error::Panic` early return, so a wrapped FFI function can never unwind
+// into C.
+
+/// Runs an expression inside `catch_unwind`, converting a caught panic into
+/// `Error::Panic` (set as the last error) and returning `$err_val`.
+///
+/// The expression must be wrapped in `AssertUnwindSafe` implicitly by this
+/// macro; callers don't need to do that themselves.
+#[macro_export]
+macro_rules! call_with_result {
+    ($body:expr, $err_val:expr) => {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| $body)) {
+            Ok(value) => value,
+            Err(payload) => {
+                let msg = $crate::macros::panic_message(&payload);
+                $crate::Error::Panic(msg).set_last();
+                return $err_val;
+            }
+        }
+    };
+}
+
+/// Like [`call_with_result!`], but the body itself already performs early
+/// returns (e.g. via other `_or_return_` macros) and only needs panic-safety
+/// wrapped around it; the result of the closure is returned directly rather
+/// than matched against `$err_val` on success.
+#[macro_export]
+macro_rules! call_with_output {
+    ($err_val:expr, $body:block) => {
+        $crate::call_with_result!($body, $err_val)
+    };
+}
+
+/// Alias for [`call_with_result!`], named for call sites that want to read
+/// as "catch any panic crossing this FFI boundary" rather than "run this
+/// body and handle its Result" - purely a naming choice, the panic handling
+/// (downcast to `&str`/`String`, `Error::Panic` via `Error::set_last()`,
+/// caller-supplied sentinel) is identical.
+#[macro_export]
+macro_rules! catch_ffi {
+    ($body:expr, $err_val:expr) => {
+        $crate::call_with_result!($body, $err_val)
+    };
+}
+
+/// Extracts a human-readable message from a caught panic payload.
+///
+/// Handles the two payload shapes produced by `panic!("...")` and
+/// `panic!("{}", ...)`/explicit `String` panics; falls back to a generic
+/// message for anything else (e.g. a panic with a non-string payload).
+#[doc(hidden)]
+pub fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Installs a process-wide panic hook that suppresses the default panic
+/// print to stderr, idempotently (safe to call from every library entry
+/// point; only the first call installs anything).
+///
+/// `call_with_result!`/`call_with_output!` already stop a panic from
+/// unwinding into C, but `catch_unwind` alone doesn't silence the default
+/// hook's `thread '...' panicked at ...` message - the payload is still
+/// recorded as `Error::Panic` via [`crate::Error::set_last`], so hosts that
+/// don't want that line on their stderr (e.g. a GUI app with no console, or
+/// a library embedded in another process) can call this once at startup to
+/// make panic reporting go exclusively through the cimpl error channel.
+pub fn install_panic_hook() {
+    static INSTALLED: std::sync::Once = std::sync::Once::new();
+    INSTALLED.call_once(|| {
+        std::panic::set_hook(Box::new(|_info| {}));
+    });
+}
+
+// ============================================================================
+// Out-Parameter Error Reporting (ExternError)
+// ============================================================================
+//
+// The thread-local last error is fragile when a host marshals calls across a
+// thread pool or interleaves them. These macros report through a caller-owned
+// `*mut ExternError` out-parameter instead, giving each call its own
+// unambiguous error channel.
+
+/// Like [`ok_or_return!`], but also writes the error into a `*mut ExternError`
+/// out-parameter (in addition to the thread-local last error).
+///
+/// ```rust,ignore
+/// #[no_mangle]
+/// pub extern "C" fn mylib_parse(s: *const c_char, out_err: *mut ExternError) -> *mut MyType {
+///     let s = cstr_or_return_null!(s);
+///     let value = ok_or_return_out_err!(MyType::parse(&s), std::ptr::null_mut(), out_err);
+///     box_tracked!(value)
+/// }
+/// ```
+#[macro_export]
+macro_rules! ok_or_return_out_err {
+    ($result:expr, $err_val:expr, $out_err:expr) => {
+        match $result {
+            Ok(value) => value,
+            Err(err) => {
+                let err: $crate::Error = err.into();
+                err.write_extern_error($out_err);
+                return $err_val;
+            }
+        }
+    };
+}
+
+/// Alias for [`ok_or_return_out_err!`], matching the name some
+/// binding-generator templates expect.
+#[macro_export]
+macro_rules! ok_or_return_err {
+    ($result:expr, $err_val:expr, $out_err:expr) => {
+        $crate::ok_or_return_out_err!($result, $err_val, $out_err)
+    };
+}
+
+/// Combines panic-catching with `ExternError` reporting: runs `$body` inside
+/// `catch_unwind`, and on a caught panic fills `$out_err` with `Error::Panic`
+/// and returns `$err_val`. The body is expected to report its own errors via
+/// `ok_or_return_out_err!`; this only guards against an unwind escaping.
+#[macro_export]
+macro_rules! with_extern_error {
+    ($out_err:expr, $err_val:expr, $body:expr) => {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| $body)) {
+            Ok(value) => value,
+            Err(payload) => {
+                let msg = $crate::macros::panic_message(&payload);
+                $crate::Error::Panic(msg).write_extern_error($out_err);
+                return $err_val;
+            }
+        }
+    };
+}
+
+// ============================================================================
+// Tagged-Union Result (CimplResult)
+// ============================================================================
+//
+// `ExternError` and the thread-local last error both report failure
+// out-of-band from the return value. `CimplResult` instead carries success
+// or failure entirely within the value returned from the call, which is
+// easier to bind safely from managed languages and doesn't depend on calls
+// not being interleaved across threads.
+
+/// Packages a `Result<T, E>` into a [`CimplResult`](crate::error::CimplResult),
+/// boxing and tracking the success value exactly like `box_tracked!` would.
+///
+/// ```rust,ignore
+/// #[no_mangle]
+/// pub extern "C" fn mylib_parse(s: *const c_char) -> CimplResult {
+///     let s = cstr_or_return!(s, CimplResult::err(Error::NullParameter("s")));
+///     ok_or_return_result!(MyType::parse(&s))
+/// }
+/// ```
+#[macro_export]
+macro_rules! ok_or_return_result {
+    ($result:expr) => {{
+        match $result {
+            Ok(value) => {
+                let ptr = $crate::box_tracked!(value) as *mut std::ffi::c_void;
+                $crate::error::CimplResult::ok(ptr)
+            }
+            Err(err) => {
+                let err: $crate::Error = err.into();
+                $crate::error::CimplResult::err(err)
+            }
+        }
+    }};
+}
+
+// ============================================================================
+// Stable Error Codes (for errors converted via Error::from_error)
+// ============================================================================
+
+/// Registers stable `u32` codes for a set of `Error::from_error`-wrapped
+/// variant names, so bindings can `switch` on [`cimpl::Error::code()`]
+/// instead of parsing the variant out of the message string. Call once at
+/// startup, before any matching errors are converted.
+///
+/// Each entry is also recorded in the runtime registry a binding generator
+/// can enumerate via [`cimpl_error_code_count()`]/[`cimpl_error_code_at()`],
+/// alongside an optional message template (defaulting to the variant name
+/// when omitted).
+///
+/// ```rust,ignore
+/// register_error_codes! {
+///     "InvalidLength" => 1000,
+///     "ParseError" => 1001, "failed to parse: {0}",
+/// }
+/// ```
+#[macro_export]
+macro_rules! register_error_codes {
+    () => {};
+    ($variant:literal => $code:expr, $msg:literal $(, $($rest:tt)*)?) => {
+        $crate::error::register_error_code_entry($code, $variant, $msg);
+        $($crate::register_error_codes!($($rest)*);)?
+    };
+    ($variant:literal => $code:expr $(, $($rest:tt)*)?) => {
+        $crate::error::register_error_code_entry($code, $variant, $variant);
+        $($crate::register_error_codes!($($rest)*);)?
+    };
+}
+
+/// Alias for [`register_error_codes!`], named to match the macro this
+/// request's binding-generator templates expect - behavior is identical.
+#[macro_export]
+macro_rules! define_error_codes {
+    ($($tt:tt)*) => {
+        $crate::register_error_codes!($($tt)*)
+    };
+}
+
 // ============================================================================
 // Named Shortcuts (self-documenting for common error values)
 // ============================================================================
@@ -587,6 +946,33 @@ macro_rules! cstr_or_return_int {
     };
 }
 
+/// Convert C string to an owned Rust `String`, explicitly loss-tolerant:
+/// ill-formed UTF-8 is replaced with `U+FFFD` (`String::from_utf8_lossy`
+/// semantics) rather than treated as an error. NULL still sets
+/// `Error::NullParameter` and early-returns `$err_val`, exactly like
+/// `cstr_or_return!`.
+///
+/// `cstr_or_return!` already performs this same substitution internally
+/// (via `CStr::to_string_lossy()`), so this macro changes no behavior -
+/// it exists to give binding authors a name that says "loss-tolerant on
+/// purpose" at the call site, for text-processing functions (e.g.
+/// `secret_rot13`, `secret_reverse`, `secret_count_chars`) where that's a
+/// deliberate design choice rather than an incidental implementation detail.
+#[macro_export]
+macro_rules! cstr_lossy_or_return {
+    ($ptr:expr, $err_val:expr) => {
+        $crate::cstr_or_return!($ptr, $err_val)
+    };
+}
+
+/// Like [`cstr_lossy_or_return!`], returning NULL on a null pointer.
+#[macro_export]
+macro_rules! cstr_lossy_or_return_null {
+    ($ptr:expr) => {
+        $crate::cstr_lossy_or_return!($ptr, std::ptr::null_mut())
+    };
+}
+
 // Internal routine to convert a *const c_char to Option<String>.
 #[macro_export]
 macro_rules! cstr_option {
@@ -604,9 +990,7 @@ macro_rules! cstr_option {
             match std::ffi::CStr::from_bytes_until_nul(bytes) {
                 Ok(cstr) => Some(cstr.to_string_lossy().into_owned()),
                 Err(_) => {
-                    $crate::Error::set_last($crate::Error::StringTooLong(
-                        stringify!($ptr).to_string(),
-                    ));
+                    $crate::Error::set_last($crate::Error::StringTooLong(stringify!($ptr)));
                     None
                 }
             }
@@ -636,3 +1020,75 @@ macro_rules! option_to_c_string {
         }
     };
 }
+
+// ============================================================================
+// JSON Serialization (IntoFfi complement for aggregate values)
+// ============================================================================
+//
+// Returning anything richer than a primitive, string, or single boxed object
+// means hand-writing a getter per field today. These macros complement
+// `box_tracked!`/`IntoFfi` for binding authors who'd rather move a whole
+// struct, enum, or `Vec` across the boundary as one JSON-encoded C string
+// (manifests, assertion lists, validation reports). They don't pull in a
+// serde dependency themselves - the calling crate already needs
+// `serde`/`serde_json` in scope to have a `Serialize`/`Deserialize` value to
+// pass in, so these only assume `serde_json::...` resolves at the macro's
+// expansion site, exactly like `ok_or_return!`'s external-error mappers
+// assume the mapped-from type is already in scope.
+
+/// Serializes any `serde::Serialize` value to a JSON C string.
+///
+/// Mirrors `box_tracked!`'s "just do it" style: serializing ordinary data
+/// (structs, enums, `Vec`s) practically never fails, so this panics rather
+/// than threading a sentinel through every call site. Use
+/// [`serialized_or_return_null!`] instead if the value's `Serialize` impl can
+/// genuinely fail (e.g. non-string map keys) and the caller needs a clean
+/// error return instead of a panic.
+#[macro_export]
+macro_rules! json_tracked {
+    ($value:expr) => {{
+        let json = serde_json::to_string(&$value)
+            .expect("json_tracked!: value failed to serialize to JSON");
+        $crate::to_c_string(json)
+    }};
+}
+
+/// Like [`json_tracked!`], but returns `NULL` (after setting the last error)
+/// instead of panicking if serialization fails.
+#[macro_export]
+macro_rules! serialized_or_return_null {
+    ($value:expr) => {
+        match serde_json::to_string(&$value) {
+            Ok(json) => $crate::to_c_string(json),
+            Err(e) => {
+                $crate::Error::Other(format!("JSON serialize error: {}", e)).set_last();
+                return std::ptr::null_mut();
+            }
+        }
+    };
+}
+
+/// Reads a bounded C string and deserializes it into `$ty` via
+/// `serde_json::from_str`, or early-returns `NULL` (after setting the last
+/// error) if the pointer is null, too long, or not valid JSON for `$ty`.
+///
+/// ```rust,ignore
+/// #[no_mangle]
+/// pub extern "C" fn manifest_request_parse(json: *const c_char) -> *mut ManifestRequest {
+///     let request: ManifestRequest = from_json_or_return_null!(json, ManifestRequest);
+///     box_tracked!(request)
+/// }
+/// ```
+#[macro_export]
+macro_rules! from_json_or_return_null {
+    ($ptr:expr, $ty:ty) => {{
+        let s = $crate::cstr_or_return_null!($ptr);
+        match serde_json::from_str::<$ty>(&s) {
+            Ok(value) => value,
+            Err(e) => {
+                $crate::Error::Other(format!("JSON deserialize error: {}", e)).set_last();
+                return std::ptr::null_mut();
+            }
+        }
+    }};
+}
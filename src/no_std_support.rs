@@ -0,0 +1,175 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Support for the `no_std` feature, which replaces the seams in this crate
+//! that otherwise hard-depend on std - so cimpl-generated bindings can be
+//! linked into constrained targets such as SGX enclaves, where calls cross
+//! a trusted boundary into a runtime that isn't std. The types here only
+//! use `core` (plus `alloc` for `Error`'s owned `String`/`Vec` fields), so
+//! they're safe to use even once the rest of the crate is audited for a
+//! full `#![no_std]` build.
+//!
+//! Two things in the rest of the crate assume std when this feature is off:
+//! - The thread-local "last error" slot (see [`Error::set_last`](crate::error::Error::set_last))
+//! - `std::sync::Mutex` guarding the pointer/allocation tracking tables in
+//!   [`crate::utils`]
+//!
+//! This module supplies replacements for both, built on a spin lock rather
+//! than an OS mutex (enclaves typically have no futex to block on).
+
+#![cfg(feature = "no_std")]
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::boxed::Box;
+
+use crate::error::Error;
+
+/// Abstracts the "last error" slot so it can be backed by something other
+/// than a std thread-local. Implementations must be safe to share across
+/// threads (`Sync`) since enclaves commonly run a single global instance
+/// rather than one slot per OS thread.
+pub trait ErrorSink: Sync {
+    /// Replaces the stored error, if any.
+    fn set(&self, err: Option<Error>);
+    /// Takes and clears the stored error.
+    fn take(&self) -> Option<Error>;
+    /// Reads the stored error without taking it.
+    fn with<R>(&self, f: impl FnOnce(Option<&Error>) -> R) -> R;
+}
+
+/// Minimal spin-lock-protected `Option<Error>`. The default [`ErrorSink`]
+/// under the `no_std` feature.
+pub struct SpinErrorSink {
+    locked: AtomicBool,
+    slot: UnsafeCell<Option<Error>>,
+}
+
+// SAFETY: access to `slot` is always gated by `locked`, acting as a mutex.
+unsafe impl Sync for SpinErrorSink {}
+
+impl SpinErrorSink {
+    pub const fn new() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            slot: UnsafeCell::new(None),
+        }
+    }
+
+    fn lock(&self) {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+    }
+
+    fn unlock(&self) {
+        self.locked.store(false, Ordering::Release);
+    }
+}
+
+impl Default for SpinErrorSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErrorSink for SpinErrorSink {
+    fn set(&self, err: Option<Error>) {
+        self.lock();
+        unsafe {
+            *self.slot.get() = err;
+        }
+        self.unlock();
+    }
+
+    fn take(&self) -> Option<Error> {
+        self.lock();
+        let taken = unsafe { (*self.slot.get()).take() };
+        self.unlock();
+        taken
+    }
+
+    fn with<R>(&self, f: impl FnOnce(Option<&Error>) -> R) -> R {
+        self.lock();
+        let result = f(unsafe { (*self.slot.get()).as_ref() });
+        self.unlock();
+        result
+    }
+}
+
+/// The process-wide last-error slot used by [`Error::set_last`](crate::error::Error::set_last)
+/// and friends when built with the `no_std` feature.
+pub static LAST_ERROR: SpinErrorSink = SpinErrorSink::new();
+
+/// A minimal spin-lock `Mutex<T>` replacement, used in place of
+/// `std::sync::Mutex` by [`crate::utils`]'s tracking tables under `no_std`.
+/// Not poison-aware - a panic while holding the lock leaves it permanently
+/// locked, which matches `no_std` targets that abort on panic rather than
+/// unwind.
+pub struct SpinMutex<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Sync for SpinMutex<T> {}
+
+pub struct SpinMutexGuard<'a, T> {
+    mutex: &'a SpinMutex<T>,
+}
+
+impl<T> core::ops::Deref for SpinMutexGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T> core::ops::DerefMut for SpinMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T> Drop for SpinMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.locked.store(false, Ordering::Release);
+    }
+}
+
+impl<T> SpinMutex<T> {
+    pub const fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn lock(&self) -> SpinMutexGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        SpinMutexGuard { mutex: self }
+    }
+}
+
+/// Type alias so [`crate::utils`] can write one cleanup closure type that
+/// works whether or not `no_std` is enabled.
+pub type CleanupFn = Box<dyn FnMut() + Send>;
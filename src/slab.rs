@@ -0,0 +1,220 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! The generational slot+free-list algorithm shared by [`crate::handle::HandleMap`]
+//! and [`crate::utils::PointerRegistry`]'s handle table. Both used to
+//! reimplement this from scratch and had drifted (`HandleMap` gained a
+//! random per-map id while the other stayed `u32`-generation-only); this
+//! module is the one copy of the slot bookkeeping, with each caller still
+//! free to pick its own lock primitive and generation width around it.
+//!
+//! `Slab<T, G>` does no locking of its own - callers wrap it in whatever
+//! primitive fits (`std::sync::RwLock` for `HandleMap`, the `no_std`-friendly
+//! `RegistryMutex` for `PointerRegistry`), and decide for themselves when to
+//! hold that lock across a call into user code.
+
+/// A slot in a [`Slab`]: either occupied by a value, or vacant and linked to
+/// the next free slot (or `None` if it's the tail of the free list).
+enum Slot<T> {
+    Occupied(T),
+    Vacant(Option<usize>),
+}
+
+/// A generation counter that wraps back to zero instead of overflowing,
+/// implemented for the two widths this crate's handles are packed with:
+/// `u16` (`HandleMap`, which reserves the other 16 bits of its `u64` handle
+/// for a per-map id) and `u32` (`PointerRegistry`'s handle table, which has
+/// no map id to share the handle with).
+pub(crate) trait Generation: Copy + Eq {
+    const INITIAL: Self;
+    fn next(self) -> Self;
+}
+
+impl Generation for u16 {
+    const INITIAL: u16 = 0;
+    fn next(self) -> u16 {
+        self.wrapping_add(1)
+    }
+}
+
+impl Generation for u32 {
+    const INITIAL: u32 = 0;
+    fn next(self) -> u32 {
+        self.wrapping_add(1)
+    }
+}
+
+/// A generational slot map: `insert` returns a slot index and the
+/// generation it was issued under, and every other access must present both
+/// back, so a stale index into a slot that's since been removed and reused
+/// is rejected rather than silently aliasing the new value.
+pub(crate) struct Slab<T, G: Generation = u32> {
+    slots: Vec<(G, Slot<T>)>,
+    free_head: Option<usize>,
+}
+
+impl<T, G: Generation> Default for Slab<T, G> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free_head: None,
+        }
+    }
+}
+
+impl<T, G: Generation> Slab<T, G> {
+    /// Inserts a value, returning the index and generation to pack into an
+    /// opaque handle for it.
+    pub(crate) fn insert(&mut self, value: T) -> (usize, G) {
+        if let Some(index) = self.free_head {
+            let (generation, slot) = &mut self.slots[index];
+            let next_free = match slot {
+                Slot::Vacant(next) => *next,
+                Slot::Occupied(_) => unreachable!("free list pointed at an occupied slot"),
+            };
+            self.free_head = next_free;
+            *slot = Slot::Occupied(value);
+            (index, *generation)
+        } else {
+            self.slots.push((G::INITIAL, Slot::Occupied(value)));
+            (self.slots.len() - 1, G::INITIAL)
+        }
+    }
+
+    /// Returns the value at `index` if it's occupied and still on
+    /// `generation` - `None` for a vacant slot, an out-of-range index, or a
+    /// stale generation (the slot was removed and possibly reused since).
+    pub(crate) fn get(&self, index: usize, generation: G) -> Option<&T> {
+        match self.slots.get(index) {
+            Some((slot_generation, Slot::Occupied(value))) if *slot_generation == generation => {
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Mutable counterpart to [`Slab::get`].
+    pub(crate) fn get_mut(&mut self, index: usize, generation: G) -> Option<&mut T> {
+        match self.slots.get_mut(index) {
+            Some((slot_generation, Slot::Occupied(value))) if *slot_generation == generation => {
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Takes the value out of `index`, leaving the slot vacant - without
+    /// bumping its generation or touching the free list yet. Pair with
+    /// [`Slab::restore`] (put a still-referenced value back unchanged) or
+    /// [`Slab::retire`] (permanently free the slot), once whatever needed to
+    /// run between the two (e.g. a cleanup callback) has finished.
+    ///
+    /// Panics if `index` is out of range or already vacant; callers must
+    /// validate via `get`/`get_mut` first.
+    pub(crate) fn take(&mut self, index: usize) -> T {
+        let free_head = self.free_head;
+        let (_, slot) = &mut self.slots[index];
+        match std::mem::replace(slot, Slot::Vacant(free_head)) {
+            Slot::Occupied(value) => value,
+            Slot::Vacant(_) => unreachable!("caller verified the slot was occupied"),
+        }
+    }
+
+    /// Puts a value taken via [`Slab::take`] back as occupied, under the
+    /// same generation it had before - for a value that's still referenced
+    /// elsewhere (e.g. an outstanding clone) and isn't actually being freed.
+    pub(crate) fn restore(&mut self, index: usize, value: T) {
+        self.slots[index].1 = Slot::Occupied(value);
+    }
+
+    /// Permanently retires a slot taken via [`Slab::take`]: bumps its
+    /// generation and pushes it onto the free list, so every index and
+    /// generation issued for it before this call is rejected forever.
+    pub(crate) fn retire(&mut self, index: usize) {
+        self.slots[index].0 = self.slots[index].0.next();
+        self.slots[index].1 = Slot::Vacant(self.free_head);
+        self.free_head = Some(index);
+    }
+
+    /// Iterates over every currently occupied value, skipping vacant slots -
+    /// for callers that want to summarize live entries (e.g. a leak report).
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().filter_map(|(_, slot)| match slot {
+            Slot::Occupied(value) => Some(value),
+            Slot::Vacant(_) => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_get_round_trip() {
+        let mut slab: Slab<&str, u32> = Slab::default();
+        let (index, generation) = slab.insert("hello");
+        assert_eq!(slab.get(index, generation), Some(&"hello"));
+    }
+
+    #[test]
+    fn stale_generation_after_take_and_retire_is_rejected() {
+        let mut slab: Slab<i32, u32> = Slab::default();
+        let (index, generation) = slab.insert(1);
+        slab.take(index);
+        slab.retire(index);
+        assert_eq!(slab.get(index, generation), None);
+    }
+
+    #[test]
+    fn retired_slot_is_reused_with_a_bumped_generation() {
+        let mut slab: Slab<i32, u32> = Slab::default();
+        let (index1, generation1) = slab.insert(1);
+        slab.take(index1);
+        slab.retire(index1);
+
+        let (index2, generation2) = slab.insert(2);
+        assert_eq!(index1, index2);
+        assert_ne!(generation1, generation2);
+        assert_eq!(slab.get(index1, generation1), None);
+        assert_eq!(slab.get(index2, generation2), Some(&2));
+    }
+
+    #[test]
+    fn restore_puts_a_taken_value_back_under_the_same_generation() {
+        let mut slab: Slab<i32, u32> = Slab::default();
+        let (index, generation) = slab.insert(1);
+        let value = slab.take(index);
+        slab.restore(index, value);
+        assert_eq!(slab.get(index, generation), Some(&1));
+    }
+
+    #[test]
+    fn iter_yields_only_occupied_values() {
+        let mut slab: Slab<i32, u32> = Slab::default();
+        let (index1, _) = slab.insert(1);
+        slab.insert(2);
+        slab.take(index1);
+        slab.retire(index1);
+
+        let mut remaining: Vec<i32> = slab.iter().copied().collect();
+        remaining.sort_unstable();
+        assert_eq!(remaining, vec![2]);
+    }
+
+    #[test]
+    fn u16_generation_wraps_instead_of_overflowing() {
+        assert_eq!(u16::MAX.next(), 0);
+        assert_eq!(u32::MAX.next(), 0);
+    }
+}
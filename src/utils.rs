@@ -20,52 +20,238 @@
 
 use std::{
     any::TypeId,
-    collections::HashMap,
     os::raw::c_uchar,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, Weak},
 };
 
+// `Mutex` above is std's - used both internally and in `track_arc_mutex`'s
+// signature for the caller's own `Arc<Mutex<T>>`, which always stays a real
+// `std::sync::Mutex` regardless of this crate's internal storage choice.
+// `RegistryMutex`/`TrackMap` are the internal tracking-table primitives,
+// swapped for spin-lock/alloc-only equivalents under `no_std`.
+#[cfg(not(feature = "no_std"))]
+use std::collections::HashMap as TrackMap;
+#[cfg(not(feature = "no_std"))]
+use std::sync::Mutex as RegistryMutex;
+
+#[cfg(feature = "no_std")]
+use std::collections::BTreeMap as TrackMap;
+#[cfg(feature = "no_std")]
+use crate::no_std_support::SpinMutex as RegistryMutex;
+
 use crate::error::Error;
+use crate::slab::Slab;
+
+/// Locks a `RegistryMutex`, uniformly across the std/`no_std` backings: the
+/// std `Mutex::lock()` returns a `LockResult` to unwrap, the `no_std`
+/// `SpinMutex::lock()` doesn't need poisoning at all.
+///
+/// A poisoned mutex (some earlier call panicked while holding the lock) is
+/// recovered via `PoisonError::into_inner()` rather than propagated - the
+/// tracking table itself is still perfectly usable, and a poisoned registry
+/// must never take down every later `cimple_free()`/tracking call with it.
+#[cfg(not(feature = "no_std"))]
+fn lock<T>(m: &RegistryMutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+#[cfg(feature = "no_std")]
+fn lock<T>(m: &RegistryMutex<T>) -> crate::no_std_support::SpinMutexGuard<'_, T> {
+    m.lock()
+}
 
 // ============================================================================
 // Pointer Registry - Tracks pointers with their cleanup functions
 // ============================================================================
 
 type CleanupFn = Box<dyn FnMut() + Send>;
+/// Increments an `Arc`'s strong count in place, backing `cimple_clone()`.
+type CloneFn = Box<dyn Fn() + Send>;
+/// Derives a `Weak<T>` from a live, tracked strong handle and builds the
+/// tracked entry for it, backing `cimple_downgrade()`. Returns the new weak
+/// pointer's address together with the entry to register for it.
+type DowngradeFn = Box<dyn Fn() -> (usize, TrackedPointer) + Send>;
+/// Attempts to upgrade a tracked `Weak<T>` back into an owning `Arc<T>`,
+/// building the tracked entry for the new strong handle on success,
+/// backing `cimple_upgrade()`.
+type UpgradeFn = Box<dyn Fn() -> Option<(usize, TrackedPointer)> + Send>;
+
+/// Whether a tracked entry owns the value (`Arc`/`Box`, freed via its
+/// cleanup function) or merely observes it (`Weak`, which never keeps the
+/// value alive). The shutdown leak warning reports these counts separately,
+/// since an outstanding `Weak` handle is far less likely to be the actual
+/// leak than an outstanding strong one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HandleKind {
+    Strong,
+    Weak,
+}
+
+/// A single tracked pointer's bookkeeping: its type (for `validate()`), its
+/// cleanup function, and - for `Arc`-backed strong handles - the extra
+/// operations needed to mint clones and weak handles of it.
+///
+/// `ref_count` lets more than one outstanding handle share the same tracked
+/// address: `cimple_clone()` increments both the `Arc`'s real strong count
+/// and this count, and `free()` only removes the entry (running `cleanup`
+/// one last time) once it reaches zero.
+pub struct TrackedPointer {
+    type_id: TypeId,
+    /// `std::any::type_name::<T>()` captured at track time, purely for
+    /// `PointerRegistry::snapshot()`/`leak_report()` - human-readable, but
+    /// not guaranteed stable, so it's for diagnostics only.
+    type_name: &'static str,
+    /// `std::mem::size_of::<T>()` captured at track time, for the
+    /// leak-report's approximate byte totals.
+    size: usize,
+    cleanup: CleanupFn,
+    kind: HandleKind,
+    ref_count: usize,
+    clone_fn: Option<CloneFn>,
+    downgrade_fn: Option<DowngradeFn>,
+    upgrade_fn: Option<UpgradeFn>,
+}
+
+impl TrackedPointer {
+    fn strong<T: 'static>(
+        cleanup: CleanupFn,
+        clone_fn: Option<CloneFn>,
+        downgrade_fn: Option<DowngradeFn>,
+    ) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            size: std::mem::size_of::<T>(),
+            cleanup,
+            kind: HandleKind::Strong,
+            ref_count: 1,
+            clone_fn,
+            downgrade_fn,
+            upgrade_fn: None,
+        }
+    }
+
+    fn weak<T: 'static>(cleanup: CleanupFn, upgrade_fn: UpgradeFn) -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            size: std::mem::size_of::<T>(),
+            cleanup,
+            kind: HandleKind::Weak,
+            ref_count: 1,
+            clone_fn: None,
+            downgrade_fn: None,
+            upgrade_fn: Some(upgrade_fn),
+        }
+    }
+}
+
+/// Builds the tracked entry for a pointer obtained from `Arc::into_raw()`,
+/// wiring up `clone`/`downgrade` support. Shared by `track_arc()`,
+/// `track_arc_mutex()`, and `cimple_upgrade()` (which re-tracks the `Arc`
+/// an upgraded `Weak` produces) so all three mint identical, fully
+/// clone/downgrade-capable strong handles.
+fn make_strong_entry<T: 'static>(ptr: *mut T) -> TrackedPointer {
+    let ptr_val = ptr as usize;
+    let cleanup: CleanupFn = Box::new(move || unsafe {
+        drop(Arc::from_raw(ptr_val as *const T));
+    });
+    let clone_fn: CloneFn = Box::new(move || unsafe {
+        Arc::increment_strong_count(ptr_val as *const T);
+    });
+    let downgrade_fn: DowngradeFn = Box::new(move || unsafe {
+        let arc = Arc::from_raw(ptr_val as *const T);
+        let weak = Arc::downgrade(&arc);
+        std::mem::forget(arc); // the strong handle being downgraded stays alive
+        make_weak_entry(Weak::into_raw(weak) as usize)
+    });
+    TrackedPointer::strong::<T>(cleanup, Some(clone_fn), Some(downgrade_fn))
+}
+
+/// Builds the tracked entry for a `Weak<T>` obtained from `Weak::into_raw()`,
+/// wiring up `upgrade` support. Shared by `make_strong_entry()`'s
+/// `downgrade_fn` and nothing else - there is exactly one way a weak handle
+/// comes into being.
+fn make_weak_entry<T: 'static>(weak_ptr: usize) -> (usize, TrackedPointer) {
+    let cleanup: CleanupFn = Box::new(move || unsafe {
+        drop(Weak::from_raw(weak_ptr as *const T));
+    });
+    let upgrade_fn: UpgradeFn = Box::new(move || unsafe {
+        let weak = Weak::from_raw(weak_ptr as *const T);
+        let upgraded = weak.upgrade();
+        let _ = Weak::into_raw(weak); // this handle keeps its own weak reference
+        upgraded.map(|arc| {
+            let new_ptr = Arc::into_raw(arc) as usize;
+            (new_ptr, make_strong_entry::<T>(new_ptr as *mut T))
+        })
+    });
+    (
+        weak_ptr,
+        TrackedPointer::weak::<T>(cleanup, upgrade_fn),
+    )
+}
+
+/// Packs a slab index and generation into the opaque `u64` handed to C.
+fn pack_handle(index: u32, generation: u32) -> u64 {
+    (index as u64) | ((generation as u64) << 32)
+}
+
+/// Reverses `pack_handle`.
+fn unpack_handle(handle: u64) -> (u32, u32) {
+    ((handle & 0xFFFF_FFFF) as u32, (handle >> 32) as u32)
+}
+
+/// One row of [`PointerRegistry::snapshot()`]: every currently tracked
+/// pointer/handle of a given type, grouped together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackedEntrySnapshot {
+    pub type_name: &'static str,
+    pub count: usize,
+    pub total_bytes: usize,
+}
 
 /// Registry that tracks pointers allocated from Rust and passed to C.
 /// Each pointer is associated with its type and a cleanup function,
 /// enabling type validation and universal freeing via `cimple_free()`.
 pub struct PointerRegistry {
-    tracked: Mutex<HashMap<usize, (TypeId, CleanupFn)>>,
+    tracked: RegistryMutex<TrackMap<usize, TrackedPointer>>,
+    /// Opt-in alternative to `tracked`: instead of keying entries by their
+    /// raw pointer address (which a later allocation can legitimately
+    /// reuse once freed, defeating `validate()`'s type check), `track_*_handle()`
+    /// stores the entry in this slab and hands back an opaque, generation-tagged
+    /// `u64` that `validate_handle()`/`free_handle()` can never mistake for a
+    /// stale handle into a recycled slot. The slot+generation+free-list
+    /// bookkeeping is [`crate::slab::Slab`], shared with `handle::HandleMap` -
+    /// this registry just wraps it in `RegistryMutex` (rather than `RwLock`,
+    /// which has no `no_std`-friendly equivalent here) and uses a 32-bit
+    /// generation, since there's no per-map id to share the handle's bits with.
+    handles: RegistryMutex<Slab<TrackedPointer, u32>>,
 }
 
 impl PointerRegistry {
     fn new() -> Self {
         Self {
-            tracked: Mutex::new(HashMap::new()),
+            tracked: RegistryMutex::new(TrackMap::new()),
+            handles: RegistryMutex::new(Slab::default()),
         }
     }
 
-    /// Track a pointer with its type and cleanup function
-    fn track(&self, ptr: usize, type_id: TypeId, cleanup: CleanupFn) {
+    /// Track a pointer under its already-built entry
+    fn track(&self, ptr: usize, entry: TrackedPointer) {
         if ptr != 0 {
-            self.tracked
-                .lock()
-                .unwrap()
-                .insert(ptr, (type_id, cleanup));
+            lock(&self.tracked).insert(ptr, entry);
         }
     }
 
     /// Validate that a pointer is tracked and has the expected type
     pub fn validate(&self, ptr: usize, expected_type: TypeId) -> Result<(), Error> {
         if ptr == 0 {
-            return Err(Error::NullParameter("pointer".to_string()));
+            return Err(Error::NullParameter("pointer"));
         }
 
-        let tracked = self.tracked.lock().unwrap();
+        let tracked = lock(&self.tracked);
         match tracked.get(&ptr) {
-            Some((actual_type, _)) if *actual_type == expected_type => Ok(()),
+            Some(entry) if entry.type_id == expected_type => Ok(()),
             Some(_) => Err(Error::WrongHandleType(ptr as u64)),
             None => Err(Error::InvalidHandle(ptr as u64)),
         }
@@ -77,25 +263,193 @@ impl PointerRegistry {
             return Ok(()); // NULL is always safe
         }
 
-        let mut cleanup = {
-            let mut tracked = self.tracked.lock().unwrap();
+        let mut entry = {
+            let mut tracked = lock(&self.tracked);
             match tracked.remove(&ptr) {
-                Some((_, cleanup)) => cleanup,
+                Some(entry) => entry,
+                None => return Err(Error::InvalidHandle(ptr as u64)),
+            }
+        }; // Release lock before cleanup
+
+        entry.ref_count -= 1;
+        (entry.cleanup)(); // Run the cleanup function - one decrement/drop per handle
+
+        if entry.ref_count > 0 {
+            // Other clones of this same handle are still outstanding - keep
+            // tracking the address so they can each be freed in turn.
+            lock(&self.tracked).insert(ptr, entry);
+        }
+        Ok(())
+    }
+
+    /// Mint a second owning handle for a strong (`Arc`-backed) entry,
+    /// incrementing both the real `Arc` strong count and this registry's
+    /// own `ref_count` for it. Returns the same address back - a clone of
+    /// an `Arc` is indistinguishable from the original at the pointer level.
+    pub fn clone_handle(&self, ptr: usize) -> Result<usize, Error> {
+        if ptr == 0 {
+            return Err(Error::NullParameter("pointer"));
+        }
+
+        let mut tracked = lock(&self.tracked);
+        match tracked.get_mut(&ptr) {
+            Some(entry) if entry.kind == HandleKind::Strong => match &entry.clone_fn {
+                Some(clone_fn) => {
+                    clone_fn();
+                    entry.ref_count += 1;
+                    Ok(ptr)
+                }
+                None => Err(Error::WrongHandleType(ptr as u64)),
+            },
+            Some(_) => Err(Error::WrongHandleType(ptr as u64)),
+            None => Err(Error::InvalidHandle(ptr as u64)),
+        }
+    }
+
+    /// Derive and track a new `Weak` handle from a strong entry.
+    pub fn downgrade(&self, ptr: usize) -> Result<usize, Error> {
+        if ptr == 0 {
+            return Err(Error::NullParameter("pointer"));
+        }
+
+        let (weak_ptr, weak_entry) = {
+            let tracked = lock(&self.tracked);
+            match tracked.get(&ptr) {
+                Some(entry) if entry.kind == HandleKind::Strong => match &entry.downgrade_fn {
+                    Some(downgrade_fn) => downgrade_fn(),
+                    None => return Err(Error::WrongHandleType(ptr as u64)),
+                },
+                Some(_) => return Err(Error::WrongHandleType(ptr as u64)),
                 None => return Err(Error::InvalidHandle(ptr as u64)),
             }
+        }; // Release lock before tracking the new entry
+
+        self.track(weak_ptr, weak_entry);
+        Ok(weak_ptr)
+    }
+
+    /// Attempt to upgrade a tracked `Weak` handle back into a tracked,
+    /// independently freeable strong handle. `Ok(None)` means the weak
+    /// handle is still valid but the value it pointed to is already gone.
+    pub fn upgrade(&self, ptr: usize) -> Result<Option<usize>, Error> {
+        if ptr == 0 {
+            return Err(Error::NullParameter("pointer"));
+        }
+
+        let upgraded = {
+            let tracked = lock(&self.tracked);
+            match tracked.get(&ptr) {
+                Some(entry) if entry.kind == HandleKind::Weak => match &entry.upgrade_fn {
+                    Some(upgrade_fn) => upgrade_fn(),
+                    None => return Err(Error::WrongHandleType(ptr as u64)),
+                },
+                Some(_) => return Err(Error::WrongHandleType(ptr as u64)),
+                None => return Err(Error::InvalidHandle(ptr as u64)),
+            }
+        }; // Release lock before tracking the new entry
+
+        match upgraded {
+            Some((new_ptr, entry)) => {
+                self.track(new_ptr, entry);
+                Ok(Some(new_ptr))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Track an entry in the generational handle slab instead of by raw
+    /// pointer address, returning the opaque `u64` handle for it.
+    fn track_handle(&self, entry: TrackedPointer) -> u64 {
+        let (index, generation) = lock(&self.handles).insert(entry);
+        pack_handle(index as u32, generation)
+    }
+
+    /// Validate that a handle is live and backed by the expected type.
+    pub fn validate_handle(&self, handle: u64, expected_type: TypeId) -> Result<(), Error> {
+        let (index, generation) = unpack_handle(handle);
+        let slab = lock(&self.handles);
+        match slab.get(index as usize, generation) {
+            Some(entry) if entry.type_id == expected_type => Ok(()),
+            Some(_) => Err(Error::WrongHandleType(handle)),
+            None => Err(Error::InvalidHandle(handle)),
+        }
+    }
+
+    /// Free a handle by calling its entry's cleanup function.
+    ///
+    /// Unlike a stale raw pointer, a stale or forged `u64` handle can never
+    /// collide with a live entry: the slot's generation is bumped every
+    /// time it's retired, so a handle from a freed (and possibly reused)
+    /// slot is always rejected with `Error::InvalidHandle` rather than
+    /// silently validating against whatever now occupies that slot.
+    pub fn free_handle(&self, handle: u64) -> Result<(), Error> {
+        let (index, generation) = unpack_handle(handle);
+        let index = index as usize;
+
+        let mut entry = {
+            let mut slab = lock(&self.handles);
+            if slab.get(index, generation).is_none() {
+                return Err(Error::InvalidHandle(handle));
+            }
+            slab.take(index)
         }; // Release lock before cleanup
 
-        cleanup(); // Run the cleanup function
+        entry.ref_count -= 1;
+        (entry.cleanup)();
+
+        let mut slab = lock(&self.handles);
+        if entry.ref_count > 0 {
+            // A clone of this handle is still outstanding - restore the
+            // slot under its same generation rather than retiring it.
+            slab.restore(index, entry);
+        } else {
+            slab.retire(index);
+        }
         Ok(())
     }
+
+    /// Groups every currently tracked pointer and handle by type name, for
+    /// `leak_report()`/`assert_no_leaks()` and for tests that want to assert
+    /// the tracking tables are empty without parsing the shutdown warning.
+    pub fn snapshot(&self) -> Vec<TrackedEntrySnapshot> {
+        let mut grouped: TrackMap<&'static str, (usize, usize)> = TrackMap::new();
+
+        let by_type = |entry: &TrackedPointer, grouped: &mut TrackMap<&'static str, (usize, usize)>| {
+            let (count, bytes) = grouped.entry(entry.type_name).or_insert((0, 0));
+            *count += 1;
+            *bytes += entry.size;
+        };
+
+        for entry in lock(&self.tracked).values() {
+            by_type(entry, &mut grouped);
+        }
+        for entry in lock(&self.handles).iter() {
+            by_type(entry, &mut grouped);
+        }
+
+        grouped
+            .into_iter()
+            .map(|(type_name, (count, total_bytes))| TrackedEntrySnapshot {
+                type_name,
+                count,
+                total_bytes,
+            })
+            .collect()
+    }
 }
 
 impl Drop for PointerRegistry {
     fn drop(&mut self) {
-        let tracked = self.tracked.lock().unwrap();
+        let tracked = lock(&self.tracked);
         if !tracked.is_empty() {
+            let (strong, weak) = tracked.values().fold((0usize, 0usize), |(s, w), entry| {
+                match entry.kind {
+                    HandleKind::Strong => (s + 1, w),
+                    HandleKind::Weak => (s, w + 1),
+                }
+            });
             eprintln!(
-                "\n⚠️  WARNING: {} pointer(s) were not freed at shutdown!",
+                "\n⚠️  WARNING: {} pointer(s) were not freed at shutdown! ({strong} strong, {weak} weak)",
                 tracked.len()
             );
             eprintln!("This indicates C code did not properly free all allocated pointers.");
@@ -121,34 +475,31 @@ pub fn get_registry() -> &'static PointerRegistry {
 /// The pointer will be freed with `Box::from_raw()` when `cimple_free()` is called.
 pub fn track_box<T: 'static>(ptr: *mut T) {
     let ptr_val = ptr as usize; // Store as usize to make it Send
-    let cleanup = move || unsafe {
+    let cleanup: CleanupFn = Box::new(move || unsafe {
         drop(Box::from_raw(ptr_val as *mut T));
-    };
-    get_registry().track(ptr as usize, TypeId::of::<T>(), Box::new(cleanup));
+    });
+    get_registry().track(
+        ptr as usize,
+        TrackedPointer::strong::<T>(cleanup, None, None),
+    );
 }
 
 /// Track an Arc-wrapped pointer
 ///
 /// Use this when you allocate with `Arc::into_raw()`.
 /// The pointer will be freed with `Arc::from_raw()` when `cimple_free()` is called.
+/// Also enables `cimple_clone()` and `cimple_downgrade()` on the returned pointer.
 pub fn track_arc<T: 'static>(ptr: *mut T) {
-    let ptr_val = ptr as usize; // Store as usize to make it Send
-    let cleanup = move || unsafe {
-        drop(Arc::from_raw(ptr_val as *const T));
-    };
-    get_registry().track(ptr as usize, TypeId::of::<T>(), Box::new(cleanup));
+    get_registry().track(ptr as usize, make_strong_entry(ptr));
 }
 
 /// Track an Arc<Mutex<T>>-wrapped pointer
 ///
 /// Use this when you allocate with `Arc::into_raw(Arc::new(Mutex::new(value)))`.
 /// The pointer will be freed with `Arc::from_raw()` when `cimple_free()` is called.
+/// Also enables `cimple_clone()` and `cimple_downgrade()` on the returned pointer.
 pub fn track_arc_mutex<T: 'static>(ptr: *mut Mutex<T>) {
-    let ptr_val = ptr as usize; // Store as usize to make it Send
-    let cleanup = move || unsafe {
-        drop(Arc::from_raw(ptr_val as *const Mutex<T>));
-    };
-    get_registry().track(ptr as usize, TypeId::of::<Mutex<T>>(), Box::new(cleanup));
+    get_registry().track(ptr as usize, make_strong_entry(ptr));
 }
 
 /// Validate that a pointer is tracked and has the expected type
@@ -156,6 +507,141 @@ pub fn validate_pointer<T: 'static>(ptr: *mut T) -> Result<(), Error> {
     get_registry().validate(ptr as usize, TypeId::of::<T>())
 }
 
+/// Tracks a pointer with a custom cleanup function, for wrapper types
+/// (such as [`crate::containers::CimplBytes`]) whose teardown needs to free
+/// more than just `Box::from_raw(ptr)` - e.g. a nested heap allocation the
+/// struct points to.
+pub(crate) fn track_with_cleanup<T: 'static>(ptr: *mut T, cleanup: impl FnMut() + Send + 'static) {
+    get_registry().track(
+        ptr as usize,
+        TrackedPointer::strong::<T>(Box::new(cleanup), None, None),
+    );
+}
+
+/// Track a Box-wrapped pointer in the generational handle slab instead of by
+/// raw address, returning an opaque `u64` handle.
+///
+/// Use this instead of `track_box()` when a recycled address legitimately
+/// fooling `validate_pointer()`'s type check is a real concern for the
+/// object in question - see the module docs' "Generational Handles" section.
+/// The pointer will be freed with `Box::from_raw()` when `cimple_free_handle()`
+/// is called.
+pub fn track_box_as_handle<T: 'static>(ptr: *mut T) -> u64 {
+    let ptr_val = ptr as usize;
+    let cleanup: CleanupFn = Box::new(move || unsafe {
+        drop(Box::from_raw(ptr_val as *mut T));
+    });
+    get_registry().track_handle(TrackedPointer::strong::<T>(cleanup, None, None))
+}
+
+/// Track an Arc-wrapped pointer in the generational handle slab instead of
+/// by raw address, returning an opaque `u64` handle.
+///
+/// Use this instead of `track_arc()` when a recycled address legitimately
+/// fooling `validate_pointer()`'s type check is a real concern for the
+/// object in question - see the module docs' "Generational Handles" section.
+/// The pointer will be freed with `Arc::from_raw()` when `cimple_free_handle()`
+/// is called. Clone/downgrade are not currently supported for handle-tracked
+/// pointers.
+pub fn track_arc_as_handle<T: 'static>(ptr: *mut T) -> u64 {
+    get_registry().track_handle(make_strong_entry(ptr))
+}
+
+/// Validate that a handle returned by `track_box_as_handle()`/`track_arc_as_handle()`
+/// is still live and backed by the expected type.
+pub fn validate_handle<T: 'static>(handle: u64) -> Result<(), Error> {
+    get_registry().validate_handle(handle, TypeId::of::<T>())
+}
+
+/// Universal free function for any pointer tracked via `track_box_as_handle()`/
+/// `track_arc_as_handle()`.
+///
+/// # Returns
+/// - `Ok(())` if the handle was successfully freed
+/// - `Err(Error::InvalidHandle)` if the handle is stale, forged, or already freed -
+///   never confused with whatever now occupies the same slab slot
+pub fn free_tracked_handle(handle: u64) -> Result<(), Error> {
+    get_registry().free_handle(handle)
+}
+
+/// C-compatible wrapper for `free_tracked_handle`
+///
+/// The generational counterpart to `cimple_free()`, for pointers tracked via
+/// `track_box_as_handle()`/`track_arc_as_handle()`. A handle from a slab slot
+/// that has since been freed and reused for a different object is always
+/// rejected, unlike a raw pointer address which the allocator is free to
+/// hand back for an unrelated allocation.
+///
+/// # Returns
+/// - 0 on success
+/// - -1 if the handle was invalid, stale, or already freed
+#[no_mangle]
+pub extern "C" fn cimple_free_handle(handle: u64) -> i32 {
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| free_tracked_handle(handle)));
+
+    match result {
+        Ok(Ok(())) => 0,
+        Ok(Err(e)) => {
+            e.set_last();
+            -1
+        }
+        Err(payload) => {
+            Error::Panic(crate::macros::panic_message(&payload)).set_last();
+            -1
+        }
+    }
+}
+
+/// Mint a second, independently-freeable handle for a pointer tracked via
+/// `track_arc()`/`track_arc_mutex()`.
+///
+/// Increments the `Arc`'s strong count and registers the result as its own
+/// tracked entry; the backing allocation is only actually dropped once every
+/// outstanding strong handle - the original and every clone - has been freed
+/// with `cimple_free()`.
+///
+/// # Returns
+/// - `Ok(ptr)` - clones share the same address as the `Arc`'s data, same as
+///   a native `Arc::clone()`
+/// - `Err(Error::WrongHandleType)` if `ptr` was tracked via `track_box()`
+///   (not `Arc`-backed) or is itself a `Weak` handle
+/// - `Err(Error::InvalidHandle)` if `ptr` is not tracked at all
+pub fn clone_tracked_pointer(ptr: *mut std::ffi::c_void) -> Result<*mut std::ffi::c_void, Error> {
+    get_registry()
+        .clone_handle(ptr as usize)
+        .map(|p| p as *mut std::ffi::c_void)
+}
+
+/// Derive a tracked `Weak` handle from a pointer tracked via
+/// `track_arc()`/`track_arc_mutex()`.
+///
+/// The returned pointer is a distinct tracked entry - free it with
+/// `cimple_free()` independently of the strong handle it was derived from.
+/// Recover a strong handle from it with `upgrade_tracked_pointer()`.
+pub fn downgrade_tracked_pointer(
+    ptr: *mut std::ffi::c_void,
+) -> Result<*mut std::ffi::c_void, Error> {
+    get_registry()
+        .downgrade(ptr as usize)
+        .map(|p| p as *mut std::ffi::c_void)
+}
+
+/// Attempt to upgrade a tracked `Weak` handle (from `downgrade_tracked_pointer()`)
+/// back into a new, independently tracked strong handle.
+///
+/// # Returns
+/// - `Ok(Some(ptr))` - a new strong handle, freeable with `cimple_free()`
+///   like any other `Arc`-backed pointer
+/// - `Ok(None)` - the weak handle is valid but the value it referenced has
+///   already been dropped
+pub fn upgrade_tracked_pointer(
+    ptr: *mut std::ffi::c_void,
+) -> Result<Option<*mut std::ffi::c_void>, Error> {
+    get_registry()
+        .upgrade(ptr as usize)
+        .map(|opt| opt.map(|p| p as *mut std::ffi::c_void))
+}
+
 /// Universal free function for any tracked pointer
 ///
 /// Frees any pointer that was allocated and tracked through cimple
@@ -209,68 +695,261 @@ pub fn free_tracked_pointer(ptr: *mut u8) -> Result<(), Error> {
 /// ```
 #[no_mangle]
 pub extern "C" fn cimple_free(ptr: *mut std::ffi::c_void) -> i32 {
-    match free_tracked_pointer(ptr as *mut u8) {
-        Ok(()) => 0,
-        Err(e) => {
+    // A tracked cleanup function can be arbitrary caller-supplied Drop code
+    // (see `track_with_cleanup`), so it can panic. Catch that here rather
+    // than let it unwind across the `extern "C"` boundary, which is
+    // undefined behavior.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        free_tracked_pointer(ptr as *mut u8)
+    }));
+
+    match result {
+        Ok(Ok(())) => 0,
+        Ok(Err(e)) => {
             e.set_last();
             -1
         }
+        Err(payload) => {
+            Error::Panic(crate::macros::panic_message(&payload)).set_last();
+            -1
+        }
+    }
+}
+
+/// C-compatible wrapper for `clone_tracked_pointer`
+///
+/// # Returns
+/// - The same pointer as `ptr` on success, now backed by an extra tracked
+///   reference - free it independently with `cimple_free()`
+/// - NULL if `ptr` isn't an `Arc`-backed strong handle (check `Error::last_message()`)
+///
+/// # Example (C)
+/// ```c
+/// MyObject* shared = my_object_create();
+/// MyObject* also_shared = cimple_clone(shared);
+/// // ... hand `also_shared` to another owner ...
+/// cimple_free(shared);       // decrements the Arc's strong count
+/// cimple_free(also_shared);  // drops the Arc's backing allocation
+/// ```
+#[no_mangle]
+pub extern "C" fn cimple_clone(ptr: *mut std::ffi::c_void) -> *mut std::ffi::c_void {
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| clone_tracked_pointer(ptr)));
+
+    match result {
+        Ok(Ok(cloned)) => cloned,
+        Ok(Err(e)) => {
+            e.set_last();
+            std::ptr::null_mut()
+        }
+        Err(payload) => {
+            Error::Panic(crate::macros::panic_message(&payload)).set_last();
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// C-compatible wrapper for `downgrade_tracked_pointer`
+///
+/// Returns a tracked `Weak` handle for an `Arc`-backed pointer, or NULL on
+/// error (check `Error::last_message()`). Free the returned handle with
+/// `cimple_free()`; recover a strong handle from it with `cimple_upgrade()`.
+#[no_mangle]
+pub extern "C" fn cimple_downgrade(ptr: *mut std::ffi::c_void) -> *mut std::ffi::c_void {
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| downgrade_tracked_pointer(ptr)));
+
+    match result {
+        Ok(Ok(weak)) => weak,
+        Ok(Err(e)) => {
+            e.set_last();
+            std::ptr::null_mut()
+        }
+        Err(payload) => {
+            Error::Panic(crate::macros::panic_message(&payload)).set_last();
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// C-compatible wrapper for `upgrade_tracked_pointer`
+///
+/// Returns a new, independently tracked strong handle, or NULL if either
+/// the value was already dropped or `ptr` wasn't a valid `Weak` handle -
+/// use `Error::last_message()` to tell the two apart.
+#[no_mangle]
+pub extern "C" fn cimple_upgrade(ptr: *mut std::ffi::c_void) -> *mut std::ffi::c_void {
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| upgrade_tracked_pointer(ptr)));
+
+    match result {
+        Ok(Ok(Some(strong))) => strong,
+        Ok(Ok(None)) => std::ptr::null_mut(),
+        Ok(Err(e)) => {
+            e.set_last();
+            std::ptr::null_mut()
+        }
+        Err(payload) => {
+            Error::Panic(crate::macros::panic_message(&payload)).set_last();
+            std::ptr::null_mut()
+        }
     }
 }
 
+// ============================================================================
+// Pluggable Host Allocator
+// ============================================================================
+//
+// On Windows (and any setup where the host app and this shared library link
+// different C runtimes), a buffer `to_c_string`/`to_c_bytes` allocated with
+// Rust's global allocator must never be freed with the host's `free()` (or
+// vice versa) - the heaps are different and doing so corrupts memory. A
+// caller in that situation registers its own `alloc`/`realloc`/`free` with
+// `cimple_set_allocator()` once at startup; `to_c_string`/`to_c_bytes` then
+// allocate through it (and `free_c_string`/`free_c_bytes` free through it)
+// instead of `Box`/`CString`, so every cross-the-FFI-boundary buffer is
+// allocated and freed by the same runtime. `AllocationTracker` records which
+// path produced each pointer, so leak reporting and double-free detection
+// keep working regardless of which allocator backs the memory.
+
+/// A host-supplied allocator, registered once via `cimple_set_allocator()`.
+///
+/// Mirrors the C `malloc`/`realloc`/`free` family. `realloc` is not
+/// currently called by this crate, but is part of the struct so a single
+/// registration can also back any future resizing API without breaking
+/// callers who already filled it in.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CimplAllocator {
+    pub alloc: unsafe extern "C" fn(size: usize) -> *mut std::ffi::c_void,
+    pub realloc: unsafe extern "C" fn(ptr: *mut std::ffi::c_void, new_size: usize) -> *mut std::ffi::c_void,
+    pub free: unsafe extern "C" fn(ptr: *mut std::ffi::c_void),
+}
+
+static HOST_ALLOCATOR: std::sync::OnceLock<CimplAllocator> = std::sync::OnceLock::new();
+
+/// Registers the host allocator used by `to_c_string`/`to_c_bytes`/
+/// `free_c_string`/`free_c_bytes`, replacing the default of allocating with
+/// Rust's global allocator (`Box`/`CString`) and freeing the same way.
+///
+/// Only the first call takes effect - the allocator is meant to be set once,
+/// at startup, before any string/byte buffer crosses the FFI boundary.
+///
+/// # Returns
+/// - `0` on success
+/// - `-1` if an allocator was already registered
+#[no_mangle]
+pub extern "C" fn cimple_set_allocator(allocator: CimplAllocator) -> i32 {
+    match HOST_ALLOCATOR.set(allocator) {
+        Ok(()) => 0,
+        Err(_) => {
+            Error::Other("host allocator already registered".to_string()).set_last();
+            -1
+        }
+    }
+}
+
+fn host_allocator() -> Option<&'static CimplAllocator> {
+    HOST_ALLOCATOR.get()
+}
+
 // ============================================================================
 // Raw Pointer Allocation Tracking (for C strings and buffers)
 // ============================================================================
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum AllocationType {
+pub enum AllocationType {
     String,
     ByteArray,
 }
 
+/// One row of [`AllocationTracker::snapshot()`]: every currently tracked raw
+/// allocation of a given kind, grouped together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationSnapshot {
+    pub allocation_type: AllocationType,
+    pub count: usize,
+    pub total_bytes: usize,
+}
+
 struct AllocationInfo {
     allocation_type: AllocationType,
     size: usize,
+    /// Whether this allocation was made through the registered
+    /// `cimple_set_allocator()` host allocator, rather than Rust's global
+    /// allocator (`Box`/`CString`) - determines which one frees it.
+    host_allocated: bool,
 }
 
 pub struct AllocationTracker {
-    allocations: Mutex<HashMap<usize, AllocationInfo>>,
+    allocations: RegistryMutex<TrackMap<usize, AllocationInfo>>,
 }
 
 impl AllocationTracker {
     fn new() -> Self {
         Self {
-            allocations: Mutex::new(HashMap::new()),
+            allocations: RegistryMutex::new(TrackMap::new()),
         }
     }
 
     /// Track a new allocation
-    fn track(&self, ptr: *const u8, size: usize, allocation_type: AllocationType) {
+    fn track(&self, ptr: *const u8, size: usize, allocation_type: AllocationType, host_allocated: bool) {
         if !ptr.is_null() {
-            let mut allocations = self.allocations.lock().unwrap();
+            let mut allocations = lock(&self.allocations);
             allocations.insert(
                 ptr as usize,
                 AllocationInfo {
                     allocation_type,
                     size,
+                    host_allocated,
                 },
             );
         }
     }
 
-    /// Untrack an allocation, returning true if it was tracked
-    fn untrack(&self, ptr: *const u8) -> bool {
+    /// Untrack an allocation, returning whether it was host-allocated if it
+    /// was tracked, or `None` if it wasn't (double-free or invalid pointer).
+    fn untrack(&self, ptr: *const u8) -> Option<bool> {
         if ptr.is_null() {
-            return true; // NULL is always safe to "free"
+            return Some(false); // NULL is always safe to "free"
+        }
+        let mut allocations = lock(&self.allocations);
+        allocations.remove(&(ptr as usize)).map(|info| info.host_allocated)
+    }
+
+    /// Groups every currently tracked raw allocation by kind, for
+    /// `leak_report()`/`assert_no_leaks()` and for tests that want to assert
+    /// the tracking table is empty without parsing the shutdown warning.
+    pub fn snapshot(&self) -> Vec<AllocationSnapshot> {
+        let mut string = (0usize, 0usize);
+        let mut array = (0usize, 0usize);
+
+        for info in lock(&self.allocations).values() {
+            let (count, bytes) = match info.allocation_type {
+                AllocationType::String => &mut string,
+                AllocationType::ByteArray => &mut array,
+            };
+            *count += 1;
+            *bytes += info.size;
         }
-        let mut allocations = self.allocations.lock().unwrap();
-        allocations.remove(&(ptr as usize)).is_some()
+
+        [
+            (AllocationType::String, string),
+            (AllocationType::ByteArray, array),
+        ]
+        .into_iter()
+        .filter(|(_, (count, _))| *count > 0)
+        .map(|(allocation_type, (count, total_bytes))| AllocationSnapshot {
+            allocation_type,
+            count,
+            total_bytes,
+        })
+        .collect()
     }
 }
 
 impl Drop for AllocationTracker {
     fn drop(&mut self) {
-        let allocations = self.allocations.lock().unwrap();
+        let allocations = lock(&self.allocations);
         if !allocations.is_empty() {
             let mut string_count = 0;
             let mut string_bytes = 0;
@@ -318,16 +997,71 @@ pub fn get_allocations() -> &'static AllocationTracker {
     ALLOCATIONS.get_or_init(AllocationTracker::new)
 }
 
+// ============================================================================
+// Leak Reporting API (queryable alternative to the stderr-only Drop warnings)
+// ============================================================================
+
+/// Formats [`get_registry()`] and [`get_allocations()`]'s current snapshots
+/// into a human-readable multi-line report, or `None` if nothing is
+/// currently outstanding in either table.
+///
+/// This is the same information the `Drop` impls print at shutdown, but
+/// available on demand - e.g. from a `#[test]` that wants to print
+/// diagnostics on failure without waiting for the process to exit.
+pub fn leak_report() -> Option<String> {
+    let pointers = get_registry().snapshot();
+    let allocations = get_allocations().snapshot();
+    if pointers.is_empty() && allocations.is_empty() {
+        return None;
+    }
+
+    let mut report = String::from("outstanding cimpl allocations:\n");
+    for entry in &pointers {
+        report.push_str(&format!(
+            "  - {} x {} (~{} bytes)\n",
+            entry.count, entry.type_name, entry.total_bytes
+        ));
+    }
+    for entry in &allocations {
+        report.push_str(&format!(
+            "  - {} x {:?} (~{} bytes)\n",
+            entry.count, entry.allocation_type, entry.total_bytes
+        ));
+    }
+    Some(report)
+}
+
+/// Asserts that no tracked pointer, handle, or raw allocation is currently
+/// outstanding.
+///
+/// Intended for `#[test]` code that creates FFI objects and exercises
+/// bindings against them: call this at the end of the test instead of
+/// relying on the `Drop` impls' stderr warnings (which a test runner's
+/// output capture can swallow, and which can't fail the test itself).
+///
+/// # Returns
+/// - `Ok(())` if both tracking tables are empty
+/// - `Err(report)` - the same text [`leak_report()`] would print, naming
+///   every outstanding type and how many of each remain
+pub fn assert_no_leaks() -> Result<(), String> {
+    match leak_report() {
+        Some(report) => Err(report),
+        None => Ok(()),
+    }
+}
+
 // Public API for tracking allocations
-pub fn track_string_allocation(ptr: *const i8, len: usize) {
-    get_allocations().track(ptr as *const u8, len, AllocationType::String);
+pub fn track_string_allocation(ptr: *const i8, len: usize, host_allocated: bool) {
+    get_allocations().track(ptr as *const u8, len, AllocationType::String, host_allocated);
 }
 
-pub fn track_bytes_allocation(ptr: *const u8, len: usize) {
-    get_allocations().track(ptr, len, AllocationType::ByteArray);
+pub fn track_bytes_allocation(ptr: *const u8, len: usize, host_allocated: bool) {
+    get_allocations().track(ptr, len, AllocationType::ByteArray, host_allocated);
 }
 
-pub fn untrack_allocation(ptr: *const u8) -> bool {
+/// Untracks `ptr`, returning whether it was host-allocated if it was
+/// tracked, or `None` if it wasn't (double-free or invalid pointer).
+pub fn untrack_allocation(ptr: *const u8) -> Option<bool> {
     get_allocations().untrack(ptr)
 }
 
@@ -387,10 +1121,10 @@ pub unsafe fn is_safe_buffer_size(size: usize, ptr: *const c_uchar) -> bool {
 pub unsafe fn safe_slice_from_raw_parts(
     ptr: *const c_uchar,
     len: usize,
-    param_name: &str,
+    param_name: &'static str,
 ) -> Result<&[u8], Error> {
     if ptr.is_null() {
-        return Err(Error::NullParameter(param_name.to_string()));
+        return Err(Error::NullParameter(param_name));
     }
 
     if !is_safe_buffer_size(len, ptr) {
@@ -402,32 +1136,159 @@ pub unsafe fn safe_slice_from_raw_parts(
     Ok(std::slice::from_raw_parts(ptr, len))
 }
 
+// ============================================================================
+// Caller-Provided Buffer Encoding (zero-allocation alternative to to_c_string/to_c_bytes)
+// ============================================================================
+
+/// Writes `s` plus a trailing NUL into a caller-provided buffer, without
+/// allocating.
+///
+/// Writes at most `cap` bytes. If `out_len` is non-null, it is always set to
+/// the number of bytes the full value needs (including the NUL), so callers
+/// can query the required size up front (e.g. by calling with `cap == 0`)
+/// and retry with a large-enough buffer.
+///
+/// # Returns
+/// * `0` (`ErrorCode::Ok`) on success
+/// * `ErrorCode::NullParameter as i32` if `out` is null and `cap > 0`
+/// * `ErrorCode::BufferTooSmall as i32` if `cap` is too small - nothing is
+///   written to `out` in this case
+///
+/// # Safety
+/// `out` must be valid for writes of `cap` bytes, and `out_len`, if non-null,
+/// must be valid for a single `usize` write.
+pub unsafe fn write_cstr_to_buf(
+    s: &str,
+    out: *mut std::os::raw::c_char,
+    cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    let needed = s.len() + 1; // +1 for the trailing NUL
+    if !out_len.is_null() {
+        *out_len = needed;
+    }
+
+    if needed > cap {
+        return crate::error::ErrorCode::BufferTooSmall as i32;
+    }
+
+    if out.is_null() {
+        return crate::error::ErrorCode::NullParameter as i32;
+    }
+
+    std::ptr::copy_nonoverlapping(s.as_ptr() as *const std::os::raw::c_char, out, s.len());
+    *out.add(s.len()) = 0;
+    0
+}
+
+/// Writes `bytes` into a caller-provided buffer, without allocating.
+///
+/// Writes at most `cap` bytes. If `out_len` is non-null, it is always set to
+/// the number of bytes the full value needs, so callers can query the
+/// required size up front and retry with a large-enough buffer.
+///
+/// # Returns
+/// * `0` (`ErrorCode::Ok`) on success
+/// * `ErrorCode::NullParameter as i32` if `out` is null and `bytes` is non-empty
+/// * `ErrorCode::BufferTooSmall as i32` if `cap` is too small - nothing is
+///   written to `out` in this case
+///
+/// # Safety
+/// `out` must be valid for writes of `cap` bytes, and `out_len`, if non-null,
+/// must be valid for a single `usize` write.
+pub unsafe fn write_bytes_to_buf(
+    bytes: &[u8],
+    out: *mut u8,
+    cap: usize,
+    out_len: *mut usize,
+) -> i32 {
+    let needed = bytes.len();
+    if !out_len.is_null() {
+        *out_len = needed;
+    }
+
+    if needed > cap {
+        return crate::error::ErrorCode::BufferTooSmall as i32;
+    }
+
+    if out.is_null() && needed > 0 {
+        return crate::error::ErrorCode::NullParameter as i32;
+    }
+
+    std::ptr::copy_nonoverlapping(bytes.as_ptr(), out, needed);
+    0
+}
+
 /// Converts a Rust String to a C string (*mut c_char)
 ///
 /// The returned pointer is tracked for allocation safety and MUST be freed
 /// by calling the appropriate free function (e.g., `c2pa_string_free`).
 ///
+/// A C string cannot contain an interior NUL byte, the same guarantee
+/// `CString::new` enforces. If `s` contains one, this sets `Error::InteriorNul`
+/// (recoverable via `Error::last_code`/`last_message`) and returns null rather
+/// than silently truncating at the NUL. Use [`to_c_string_lossy`] if
+/// best-effort output (with interior NULs replaced by spaces) is acceptable instead.
+///
 /// # Arguments
 /// * `s` - The Rust String to convert
 ///
 /// # Returns
 /// * `*mut c_char` - Pointer to the C string, or null on error
 ///
+/// If a host allocator was registered via [`cimple_set_allocator`], the
+/// returned buffer is allocated through it instead of `CString`, so it can
+/// be freed safely by a C runtime that doesn't share Rust's global
+/// allocator.
+///
 /// # Safety
 /// The returned pointer must be freed exactly once by C code
 pub fn to_c_string(s: String) -> *mut std::os::raw::c_char {
     use std::ffi::CString;
     let len = s.len();
-    match CString::new(s) {
-        Ok(c_str) => {
-            let ptr = c_str.into_raw();
-            track_string_allocation(ptr, len + 1); // +1 for null terminator
-            ptr
+    let c_str = match CString::new(s) {
+        Ok(c_str) => c_str,
+        Err(e) => {
+            Error::InteriorNul(e.nul_position()).set_last();
+            return std::ptr::null_mut();
         }
-        Err(_) => std::ptr::null_mut(),
+    };
+
+    if let Some(alloc) = host_allocator() {
+        let bytes_with_nul = c_str.into_bytes_with_nul();
+        let ptr = unsafe { (alloc.alloc)(bytes_with_nul.len()) } as *mut u8;
+        if ptr.is_null() {
+            Error::Other("host allocator returned null".to_string()).set_last();
+            return std::ptr::null_mut();
+        }
+        unsafe { std::ptr::copy_nonoverlapping(bytes_with_nul.as_ptr(), ptr, bytes_with_nul.len()) };
+        track_string_allocation(ptr as *const i8, bytes_with_nul.len(), true);
+        ptr as *mut std::os::raw::c_char
+    } else {
+        let ptr = c_str.into_raw();
+        track_string_allocation(ptr, len + 1, false); // +1 for null terminator
+        ptr
     }
 }
 
+/// Like [`to_c_string`], but replaces any interior NUL bytes with spaces
+/// instead of failing, for callers that prefer best-effort output over a
+/// clean, detectable failure.
+///
+/// Never returns null due to an interior NUL - only a registered host
+/// allocator (see [`cimple_set_allocator`]) returning null would do that.
+pub fn to_c_string_lossy(mut s: String) -> *mut std::os::raw::c_char {
+    // SAFETY: replacing a NUL byte with another ASCII byte preserves UTF-8 validity.
+    unsafe {
+        for b in s.as_bytes_mut() {
+            if *b == 0 {
+                *b = b' ';
+            }
+        }
+    }
+    to_c_string(s)
+}
+
 /// Converts a `Vec <u8>` to a tracked C byte array pointer
 ///
 /// The returned pointer is tracked for allocation safety and MUST be freed
@@ -439,13 +1300,29 @@ pub fn to_c_string(s: String) -> *mut std::os::raw::c_char {
 /// # Returns
 /// * `*const c_uchar` - Pointer to the byte array
 ///
+/// If a host allocator was registered via [`cimple_set_allocator`], the
+/// returned buffer is allocated through it instead of `Box`, so it can be
+/// freed safely by a C runtime that doesn't share Rust's global allocator.
+///
 /// # Safety
 /// The returned pointer must be freed exactly once by calling `free_c_bytes`
 pub fn to_c_bytes(bytes: Vec<u8>) -> *const c_uchar {
     let len = bytes.len();
-    let ptr = Box::into_raw(bytes.into_boxed_slice()) as *const c_uchar;
-    track_bytes_allocation(ptr, len);
-    ptr
+
+    if let Some(alloc) = host_allocator() {
+        let ptr = unsafe { (alloc.alloc)(len) } as *mut u8;
+        if ptr.is_null() {
+            Error::Other("host allocator returned null".to_string()).set_last();
+            return std::ptr::null();
+        }
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, len) };
+        track_bytes_allocation(ptr, len, true);
+        ptr as *const c_uchar
+    } else {
+        let ptr = Box::into_raw(bytes.into_boxed_slice()) as *const c_uchar;
+        track_bytes_allocation(ptr, len, false);
+        ptr
+    }
 }
 
 /// Safely frees a tracked C string
@@ -461,6 +1338,9 @@ pub fn to_c_bytes(bytes: Vec<u8>) -> *const c_uchar {
 /// * `true` if the string was tracked and freed successfully, or if ptr was NULL
 /// * `false` if the string was not tracked (double-free or invalid pointer)
 ///
+/// Frees through the registered host allocator if the string was allocated
+/// that way (see [`cimple_set_allocator`]); otherwise via `CString`.
+///
 /// # Safety
 /// This function is safe to call with NULL or invalid pointers - it will not panic
 pub unsafe fn free_c_string(ptr: *mut std::os::raw::c_char) -> bool {
@@ -470,15 +1350,24 @@ pub unsafe fn free_c_string(ptr: *mut std::os::raw::c_char) -> bool {
         return true; // NULL is always safe
     }
 
-    if untrack_allocation(ptr as *const u8) {
-        drop(CString::from_raw(ptr));
-        true
-    } else {
-        eprintln!(
-            "WARNING: Attempt to free untracked or already-freed string pointer: {:p}",
-            ptr
-        );
-        false
+    match untrack_allocation(ptr as *const u8) {
+        Some(true) => {
+            (host_allocator().expect("host-allocated pointer with no host allocator").free)(
+                ptr as *mut std::ffi::c_void,
+            );
+            true
+        }
+        Some(false) => {
+            drop(CString::from_raw(ptr));
+            true
+        }
+        None => {
+            eprintln!(
+                "WARNING: Attempt to free untracked or already-freed string pointer: {:p}",
+                ptr
+            );
+            false
+        }
     }
 }
 
@@ -495,6 +1384,9 @@ pub unsafe fn free_c_string(ptr: *mut std::os::raw::c_char) -> bool {
 /// * `true` if the array was tracked and freed successfully, or if ptr was NULL
 /// * `false` if the array was not tracked (double-free or invalid pointer)
 ///
+/// Frees through the registered host allocator if the array was allocated
+/// that way (see [`cimple_set_allocator`]); otherwise via `Box`.
+///
 /// # Safety
 /// This function is safe to call with NULL or invalid pointers - it will not panic
 pub unsafe fn free_c_bytes(ptr: *const c_uchar) -> bool {
@@ -502,15 +1394,24 @@ pub unsafe fn free_c_bytes(ptr: *const c_uchar) -> bool {
         return true; // NULL is always safe
     }
 
-    if untrack_allocation(ptr) {
-        drop(Box::from_raw(ptr as *mut c_uchar));
-        true
-    } else {
-        eprintln!(
-            "WARNING: Attempt to free untracked or already-freed byte array pointer: {:p}",
-            ptr
-        );
-        false
+    match untrack_allocation(ptr) {
+        Some(true) => {
+            (host_allocator().expect("host-allocated pointer with no host allocator").free)(
+                ptr as *mut std::ffi::c_void,
+            );
+            true
+        }
+        Some(false) => {
+            drop(Box::from_raw(ptr as *mut c_uchar));
+            true
+        }
+        None => {
+            eprintln!(
+                "WARNING: Attempt to free untracked or already-freed byte array pointer: {:p}",
+                ptr
+            );
+            false
+        }
     }
 }
 
@@ -536,6 +1437,204 @@ mod tests {
         assert!(!result2);
     }
 
+    #[test]
+    fn test_cimple_free_catches_panicking_cleanup() {
+        struct PanicsOnDrop;
+        impl Drop for PanicsOnDrop {
+            fn drop(&mut self) {
+                panic!("boom");
+            }
+        }
+
+        let ptr = Box::into_raw(Box::new(PanicsOnDrop));
+        track_box(ptr);
+
+        // The cleanup closure panics while dropping the boxed value; this
+        // must be caught at the `extern "C"` boundary rather than unwind
+        // into the caller.
+        let result = cimple_free(ptr as *mut std::ffi::c_void);
+        assert_eq!(result, -1);
+        assert!(matches!(Error::take_last(), Some(Error::Panic(_))));
+    }
+
+    #[test]
+    fn test_cimple_clone_shares_one_drop() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        struct CountsDrops;
+        impl Drop for CountsDrops {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let ptr = Arc::into_raw(Arc::new(CountsDrops)) as *mut CountsDrops;
+        track_arc(ptr);
+
+        let cloned = cimple_clone(ptr as *mut std::ffi::c_void);
+        assert_eq!(cloned, ptr as *mut std::ffi::c_void);
+
+        // Freeing the original must not drop the value while the clone is
+        // still outstanding.
+        assert!(free_tracked_pointer(ptr as *mut u8).is_ok());
+        assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+
+        // Freeing the clone drops the last strong reference.
+        assert!(free_tracked_pointer(cloned as *mut u8).is_ok());
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_cimple_clone_rejects_box_tracked_pointer() {
+        let ptr = Box::into_raw(Box::new(42i32));
+        track_box(ptr);
+
+        let cloned = cimple_clone(ptr as *mut std::ffi::c_void);
+        assert!(cloned.is_null());
+        assert!(matches!(
+            Error::take_last(),
+            Some(Error::WrongHandleType(_))
+        ));
+
+        cimple_free(ptr as *mut std::ffi::c_void);
+    }
+
+    #[test]
+    fn test_cimple_downgrade_and_upgrade_roundtrip() {
+        let ptr = Arc::into_raw(Arc::new(7i32)) as *mut i32;
+        track_arc(ptr);
+
+        let weak = cimple_downgrade(ptr as *mut std::ffi::c_void);
+        assert!(!weak.is_null());
+
+        let upgraded = cimple_upgrade(weak);
+        assert!(!upgraded.is_null());
+        assert_eq!(unsafe { *(upgraded as *const i32) }, 7);
+
+        assert_eq!(cimple_free(upgraded), 0);
+        assert_eq!(cimple_free(weak), 0);
+        assert_eq!(cimple_free(ptr as *mut std::ffi::c_void), 0);
+    }
+
+    #[test]
+    fn test_cimple_upgrade_returns_null_once_value_is_gone() {
+        let ptr = Arc::into_raw(Arc::new(String::from("gone soon"))) as *mut String;
+        track_arc(ptr);
+
+        let weak = cimple_downgrade(ptr as *mut std::ffi::c_void);
+        assert!(!weak.is_null());
+
+        // Dropping the only strong handle leaves the weak handle dangling.
+        assert_eq!(cimple_free(ptr as *mut std::ffi::c_void), 0);
+
+        let upgraded = cimple_upgrade(weak);
+        assert!(upgraded.is_null());
+        assert!(Error::take_last().is_none());
+
+        assert_eq!(cimple_free(weak), 0);
+    }
+
+    #[test]
+    fn test_handle_mode_round_trips_and_rejects_wrong_type() {
+        let ptr = Box::into_raw(Box::new(99i32));
+        let handle = track_box_as_handle(ptr);
+
+        assert!(validate_handle::<i32>(handle).is_ok());
+        assert!(matches!(
+            validate_handle::<String>(handle),
+            Err(Error::WrongHandleType(_))
+        ));
+
+        assert!(free_tracked_handle(handle).is_ok());
+    }
+
+    #[test]
+    fn test_handle_mode_rejects_stale_handle_after_reuse() {
+        let first_ptr = Box::into_raw(Box::new(1i32));
+        let first_handle = track_box_as_handle(first_ptr);
+        assert!(free_tracked_handle(first_handle).is_ok());
+
+        // Reuses the same slab slot, but with a bumped generation.
+        let second_ptr = Box::into_raw(Box::new(2i32));
+        let second_handle = track_box_as_handle(second_ptr);
+
+        assert!(matches!(
+            free_tracked_handle(first_handle),
+            Err(Error::InvalidHandle(_))
+        ));
+        assert!(free_tracked_handle(second_handle).is_ok());
+    }
+
+    #[test]
+    fn test_cimple_free_handle_catches_double_free() {
+        let ptr = Box::into_raw(Box::new("tracked via handle".to_string()));
+        let handle = track_box_as_handle(ptr);
+
+        assert_eq!(cimple_free_handle(handle), 0);
+        assert_eq!(cimple_free_handle(handle), -1);
+        assert!(matches!(Error::take_last(), Some(Error::InvalidHandle(_))));
+    }
+
+    #[test]
+    fn test_pointer_registry_snapshot_reports_and_clears_by_type() {
+        struct SnapshotProbe;
+        let ptr = Box::into_raw(Box::new(SnapshotProbe));
+        track_box(ptr);
+
+        // `type_name` is derived from this test's own local type, so it
+        // can't collide with another test's tracked objects running
+        // concurrently in the same binary.
+        let type_name = std::any::type_name::<SnapshotProbe>();
+        let before = get_registry().snapshot();
+        assert_eq!(
+            before.iter().find(|e| e.type_name == type_name).map(|e| e.count),
+            Some(1)
+        );
+
+        assert!(free_tracked_pointer(ptr as *mut u8).is_ok());
+
+        let after = get_registry().snapshot();
+        assert!(after.iter().all(|e| e.type_name != type_name));
+    }
+
+    #[test]
+    fn test_leak_report_names_outstanding_type() {
+        struct LeakReportProbe;
+        let ptr = Box::into_raw(Box::new(LeakReportProbe));
+        track_box(ptr);
+
+        let type_name = std::any::type_name::<LeakReportProbe>();
+        let report = leak_report().expect("this test's own tracked pointer is outstanding");
+        assert!(report.contains(type_name));
+
+        assert!(free_tracked_pointer(ptr as *mut u8).is_ok());
+        assert!(!leak_report().unwrap_or_default().contains(type_name));
+    }
+
+    #[test]
+    fn test_allocation_tracker_snapshot_groups_by_type() {
+        let count_before = get_allocations()
+            .snapshot()
+            .into_iter()
+            .find(|e| e.allocation_type == AllocationType::String)
+            .map(|e| e.count)
+            .unwrap_or(0);
+
+        let c_string = to_c_string("snapshot probe".to_string());
+        assert!(!c_string.is_null());
+
+        let count_after = get_allocations()
+            .snapshot()
+            .into_iter()
+            .find(|e| e.allocation_type == AllocationType::String)
+            .map(|e| e.count)
+            .unwrap_or(0);
+        assert_eq!(count_after, count_before + 1);
+
+        unsafe { free_c_string(c_string) };
+    }
+
     #[test]
     fn test_allocation_tracking_null_free() {
         // Test that freeing NULL is safe
@@ -585,10 +1684,103 @@ mod tests {
 
     #[test]
     fn test_to_c_string_with_null_byte() {
-        // Test that strings with embedded nulls return null
+        // Test that strings with embedded nulls return null and report InteriorNul
         let bad_string = "Hello\0World".to_string();
         let c_string = to_c_string(bad_string);
         assert!(c_string.is_null());
+        assert_eq!(Error::last_code(), crate::error::ErrorCode::InteriorNul as i32);
         // No need to free since it's null
     }
+
+    #[test]
+    fn test_to_c_string_lossy_with_null_byte() {
+        // Test that the lossy variant never fails on embedded nulls
+        let bad_string = "Hello\0World".to_string();
+        let c_string = to_c_string_lossy(bad_string);
+        assert!(!c_string.is_null());
+
+        unsafe { free_c_string(c_string) };
+    }
+
+    // `cimple_set_allocator` registers a process-global, set-once `OnceLock`,
+    // so it can only be exercised once for the lifetime of the test binary.
+    // These counting malloc/free shims are routed through by every other test
+    // in this module that runs afterwards (alphabetically, or in parallel),
+    // but the host-allocator path is functionally equivalent to the default
+    // one, so this is safe to leave registered for the rest of the run.
+    static HOST_ALLOC_CALLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    static HOST_FREE_CALLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    unsafe extern "C" fn counting_alloc(size: usize) -> *mut std::ffi::c_void {
+        HOST_ALLOC_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let layout = std::alloc::Layout::from_size_align(size.max(1), 1).unwrap();
+        std::alloc::alloc(layout) as *mut std::ffi::c_void
+    }
+
+    unsafe extern "C" fn counting_realloc(
+        ptr: *mut std::ffi::c_void,
+        new_size: usize,
+    ) -> *mut std::ffi::c_void {
+        let layout = std::alloc::Layout::from_size_align(1, 1).unwrap();
+        std::alloc::realloc(ptr as *mut u8, layout, new_size.max(1)) as *mut std::ffi::c_void
+    }
+
+    unsafe extern "C" fn counting_free(ptr: *mut std::ffi::c_void) {
+        HOST_FREE_CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let layout = std::alloc::Layout::from_size_align(1, 1).unwrap();
+        std::alloc::dealloc(ptr as *mut u8, layout);
+    }
+
+    #[test]
+    fn test_host_allocator_routes_string_and_bytes_through_host() {
+        let _ = cimple_set_allocator(CimplAllocator {
+            alloc: counting_alloc,
+            realloc: counting_realloc,
+            free: counting_free,
+        });
+
+        // Whether this test or an earlier one in the same binary won the
+        // race to register the allocator, it's registered for the rest of
+        // this process - conversions must now route through *some* host
+        // allocator's alloc/free pair without leaking or double-freeing.
+        let allocs_before = HOST_ALLOC_CALLS.load(std::sync::atomic::Ordering::SeqCst);
+        let frees_before = HOST_FREE_CALLS.load(std::sync::atomic::Ordering::SeqCst);
+
+        let c_string = to_c_string("host allocated".to_string());
+        assert!(!c_string.is_null());
+        assert!(unsafe { free_c_string(c_string) });
+
+        let c_bytes = to_c_bytes(vec![9, 8, 7]);
+        assert!(!c_bytes.is_null());
+        assert!(unsafe { free_c_bytes(c_bytes) });
+
+        // If *this* call won the registration race, the counts moved;
+        // if a prior test won it, they already did - either way a string
+        // and a byte array were each allocated and freed exactly once more.
+        if HOST_ALLOC_CALLS.load(std::sync::atomic::Ordering::SeqCst) > allocs_before {
+            assert_eq!(
+                HOST_FREE_CALLS.load(std::sync::atomic::Ordering::SeqCst) - frees_before,
+                2
+            );
+        }
+    }
+
+    #[test]
+    fn test_cimple_set_allocator_rejects_second_registration() {
+        // Whichever test in this binary registers first wins; every
+        // subsequent attempt - including a second call made deliberately
+        // here - must be rejected rather than silently replacing it.
+        let _ = cimple_set_allocator(CimplAllocator {
+            alloc: counting_alloc,
+            realloc: counting_realloc,
+            free: counting_free,
+        });
+        let result = cimple_set_allocator(CimplAllocator {
+            alloc: counting_alloc,
+            realloc: counting_realloc,
+            free: counting_free,
+        });
+        assert_eq!(result, -1);
+        assert!(matches!(Error::take_last(), Some(Error::Other(_))));
+    }
 }
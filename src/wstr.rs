@@ -0,0 +1,291 @@
+// Copyright 2026 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Windows wide-string (UTF-16) conversion, via a lossless WTF-8 buffer.
+//!
+//! `cstr_or_return!`/`FfiStr` assume a NUL-terminated UTF-8 `*const c_char`,
+//! which has no equivalent on Windows, where strings crossing the FFI
+//! boundary are routinely `*const u16` UTF-16 and may contain unpaired
+//! surrogates that `String`/`CStr` can't represent at all. [`Wtf8Buf`] is a
+//! `Vec<u8>`-backed buffer that stores ordinary code points as normal UTF-8
+//! and a lone surrogate (`U+D800..=U+DFFF`) as the 3-byte "generalized
+//! UTF-8" sequence that code point would take if it were allowed - so a
+//! round trip `&[u16]` -> `Wtf8Buf` -> `&[u16]` reproduces the original code
+//! units exactly, including ill-formed ones, while still offering a lossy
+//! `String` (via [`Wtf8Buf::to_string_lossy`]) for callers that only want
+//! valid UTF-8.
+//!
+//! Use [`wstr_or_return!`]/[`wstr_or_return_null!`] to borrow a `*const u16`
+//! parameter as a `Wtf8Buf`, and [`to_c_wstring`] to return one as a tracked,
+//! NUL-terminated `*mut u16`.
+
+use crate::utils::track_with_cleanup;
+
+/// Maximum number of `u16` code units scanned looking for a NUL terminator,
+/// mirroring [`crate::macros::MAX_CSTRING_LEN`] for `*const u16` input.
+pub const MAX_WCSTRING_LEN: usize = 65536;
+
+/// A WTF-8 encoded buffer: like `String`, but additionally able to hold
+/// unpaired UTF-16 surrogate code points, so converting from/to UTF-16 is
+/// lossless even for ill-formed input. See the module docs for the encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Wtf8Buf(Vec<u8>);
+
+impl Wtf8Buf {
+    /// Decodes a slice of UTF-16 code units into WTF-8. Valid surrogate
+    /// pairs combine into a single astral code point (4-byte UTF-8); a lone
+    /// high or low surrogate is stored as a 3-byte generalized UTF-8
+    /// sequence instead of being replaced or rejected.
+    pub fn from_wide(units: &[u16]) -> Self {
+        let mut buf = Vec::with_capacity(units.len());
+        let mut iter = units.iter().copied().peekable();
+        while let Some(unit) = iter.next() {
+            let code_point = match unit {
+                0xD800..=0xDBFF => match iter.peek() {
+                    Some(&low) if (0xDC00..=0xDFFF).contains(&low) => {
+                        iter.next();
+                        0x10000 + ((unit as u32 - 0xD800) << 10) + (low as u32 - 0xDC00)
+                    }
+                    _ => unit as u32,
+                },
+                other => other as u32,
+            };
+            push_code_point(&mut buf, code_point);
+        }
+        Wtf8Buf(buf)
+    }
+
+    /// Re-encodes this buffer as UTF-16 code units, re-splitting astral code
+    /// points into surrogate pairs and reproducing any stored lone
+    /// surrogate as a single ill-formed code unit.
+    pub fn to_wide(&self) -> Vec<u16> {
+        let mut units = Vec::with_capacity(self.0.len());
+        let mut i = 0;
+        while i < self.0.len() {
+            let (code_point, len) = decode_code_point(&self.0[i..]);
+            i += len;
+            if code_point < 0x10000 {
+                units.push(code_point as u16);
+            } else {
+                let c = code_point - 0x10000;
+                units.push(0xD800 + (c >> 10) as u16);
+                units.push(0xDC00 + (c & 0x3FF) as u16);
+            }
+        }
+        units
+    }
+
+    /// Converts to a valid UTF-8 `String`, substituting `U+FFFD` for every
+    /// stored lone surrogate.
+    pub fn to_string_lossy(&self) -> String {
+        let mut out = String::with_capacity(self.0.len());
+        let mut i = 0;
+        while i < self.0.len() {
+            let (code_point, len) = decode_code_point(&self.0[i..]);
+            i += len;
+            out.push(char::from_u32(code_point).unwrap_or('\u{FFFD}'));
+        }
+        out
+    }
+
+    /// The underlying WTF-8 bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Appends `code_point` to `buf` using the standard UTF-8 byte-length
+/// scheme, without rejecting the surrogate range - applied to a surrogate
+/// code point, this is exactly the "generalized UTF-8" 3-byte sequence the
+/// module docs describe.
+fn push_code_point(buf: &mut Vec<u8>, code_point: u32) {
+    if code_point < 0x80 {
+        buf.push(code_point as u8);
+    } else if code_point < 0x800 {
+        buf.push(0xC0 | (code_point >> 6) as u8);
+        buf.push(0x80 | (code_point & 0x3F) as u8);
+    } else if code_point < 0x10000 {
+        buf.push(0xE0 | (code_point >> 12) as u8);
+        buf.push(0x80 | ((code_point >> 6) & 0x3F) as u8);
+        buf.push(0x80 | (code_point & 0x3F) as u8);
+    } else {
+        buf.push(0xF0 | (code_point >> 18) as u8);
+        buf.push(0x80 | ((code_point >> 12) & 0x3F) as u8);
+        buf.push(0x80 | ((code_point >> 6) & 0x3F) as u8);
+        buf.push(0x80 | (code_point & 0x3F) as u8);
+    }
+}
+
+/// Decodes one code point starting at `bytes[0]`, returning it along with
+/// the number of bytes it occupied. Only ever called on bytes this module
+/// produced itself via [`push_code_point`], so the leading byte's high bits
+/// reliably determine the sequence length.
+fn decode_code_point(bytes: &[u8]) -> (u32, usize) {
+    let b0 = bytes[0] as u32;
+    if b0 < 0x80 {
+        (b0, 1)
+    } else if b0 & 0xE0 == 0xC0 {
+        let c = ((b0 & 0x1F) << 6) | (bytes[1] as u32 & 0x3F);
+        (c, 2)
+    } else if b0 & 0xF0 == 0xE0 {
+        let c = ((b0 & 0x0F) << 12) | ((bytes[1] as u32 & 0x3F) << 6) | (bytes[2] as u32 & 0x3F);
+        (c, 3)
+    } else {
+        let c = ((b0 & 0x07) << 18)
+            | ((bytes[1] as u32 & 0x3F) << 12)
+            | ((bytes[2] as u32 & 0x3F) << 6)
+            | (bytes[3] as u32 & 0x3F);
+        (c, 4)
+    }
+}
+
+/// Converts a [`Wtf8Buf`] into a tracked, NUL-terminated `*mut u16` for
+/// returning a wide string across FFI, mirroring [`crate::to_c_string`].
+///
+/// The returned pointer must be freed exactly once via [`crate::cimpl_free`].
+pub fn to_c_wstring(buf: &Wtf8Buf) -> *mut u16 {
+    let mut units = buf.to_wide();
+    units.push(0);
+    let len = units.len();
+    let ptr = Box::into_raw(units.into_boxed_slice()) as *mut u16;
+
+    // `len` (including the trailing NUL) is captured directly rather than
+    // recovered by rescanning for a zero code unit at cleanup time - a
+    // `Wtf8Buf` may legitimately contain an embedded U+0000 (e.g. a
+    // length-prefixed wide string like a Windows `BSTR`), which a rescan
+    // would mistake for the terminator and free fewer elements than were
+    // actually allocated. Same out-of-band-length approach `to_cimpl_bytes`/
+    // `to_cimpl_vec` use, for the same reason.
+    let ptr_val = ptr as usize;
+    let cleanup = move || unsafe {
+        drop(Box::from_raw(std::slice::from_raw_parts_mut(
+            ptr_val as *mut u16,
+            len,
+        ) as *mut [u16]));
+    };
+    track_with_cleanup(ptr, cleanup);
+    ptr
+}
+
+/// Borrows a NUL-terminated `*const u16` wide string as a [`Wtf8Buf`],
+/// scanning up to [`MAX_WCSTRING_LEN`] code units for the terminator.
+/// Returns `None` if `ptr` is null or no terminator is found in bounds.
+///
+/// # Safety
+/// `ptr` must be valid for reads of at least the code units up to and
+/// including its NUL terminator, or up to `MAX_WCSTRING_LEN` units.
+pub unsafe fn from_c_wstring(ptr: *const u16) -> Option<Wtf8Buf> {
+    if ptr.is_null() {
+        return None;
+    }
+    let units = std::slice::from_raw_parts(ptr, MAX_WCSTRING_LEN);
+    let len = units.iter().position(|&u| u == 0)?;
+    Some(Wtf8Buf::from_wide(&units[..len]))
+}
+
+/// Borrow a `*const u16` wide string as a [`Wtf8Buf`], or early-return with
+/// `$err_val` if it's null or has no NUL terminator within
+/// [`MAX_WCSTRING_LEN`] units.
+#[macro_export]
+macro_rules! wstr_or_return {
+    ($ptr:expr, $err_val:expr) => {{
+        let ptr = $ptr;
+        if ptr.is_null() {
+            $crate::Error::set_last($crate::Error::NullParameter(stringify!($ptr)));
+            return $err_val;
+        }
+        match unsafe { $crate::wstr::from_c_wstring(ptr) } {
+            Some(buf) => buf,
+            None => {
+                $crate::Error::set_last($crate::Error::StringTooLong(stringify!($ptr)));
+                return $err_val;
+            }
+        }
+    }};
+}
+
+/// Like [`wstr_or_return!`], returning NULL on error.
+#[macro_export]
+macro_rules! wstr_or_return_null {
+    ($ptr:expr) => {
+        $crate::wstr_or_return!($ptr, std::ptr::null_mut())
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_ordinary_text() {
+        let units: Vec<u16> = "hello \u{1F600}".encode_utf16().collect();
+        let buf = Wtf8Buf::from_wide(&units);
+        assert_eq!(buf.to_wide(), units);
+        assert_eq!(buf.to_string_lossy(), "hello \u{1F600}");
+    }
+
+    #[test]
+    fn round_trips_lone_high_surrogate() {
+        let units: Vec<u16> = vec![0x0041, 0xD800, 0x0042];
+        let buf = Wtf8Buf::from_wide(&units);
+        assert_eq!(buf.to_wide(), units);
+        assert_eq!(buf.to_string_lossy(), "A\u{FFFD}B");
+    }
+
+    #[test]
+    fn round_trips_lone_low_surrogate() {
+        let units: Vec<u16> = vec![0xDC00];
+        let buf = Wtf8Buf::from_wide(&units);
+        assert_eq!(buf.to_wide(), units);
+    }
+
+    #[test]
+    fn round_trips_valid_surrogate_pair() {
+        // U+1F600 GRINNING FACE as an explicit surrogate pair.
+        let units: Vec<u16> = vec![0xD83D, 0xDE00];
+        let buf = Wtf8Buf::from_wide(&units);
+        assert_eq!(buf.to_wide(), units);
+        assert_eq!(buf.to_string_lossy(), "\u{1F600}");
+    }
+
+    #[test]
+    fn from_c_wstring_reads_up_to_nul() {
+        let mut raw: Vec<u16> = "hi".encode_utf16().collect();
+        raw.push(0);
+        let buf = unsafe { from_c_wstring(raw.as_ptr()) }.unwrap();
+        assert_eq!(buf.to_string_lossy(), "hi");
+    }
+
+    #[test]
+    fn from_c_wstring_rejects_null_pointer() {
+        assert!(unsafe { from_c_wstring(std::ptr::null()) }.is_none());
+    }
+
+    #[test]
+    fn to_c_wstring_frees_the_full_allocation_with_an_embedded_nul() {
+        // A `Wtf8Buf` built from code units with an embedded U+0000 - e.g. a
+        // length-prefixed wide string like a Windows `BSTR` - must still be
+        // freed in full. If `to_c_wstring`'s cleanup rescanned for a zero
+        // code unit instead of using the captured length, it would stop at
+        // the embedded NUL and free a shorter slice than was allocated.
+        let units: Vec<u16> = vec![0x0041, 0, 0x0042];
+        let buf = Wtf8Buf::from_wide(&units);
+        let ptr = to_c_wstring(&buf);
+        assert!(!ptr.is_null());
+        let read_back = unsafe { std::slice::from_raw_parts(ptr, 4) };
+        assert_eq!(read_back, &[0x0041, 0, 0x0042, 0]);
+
+        let result = crate::cimple_free(ptr as *mut std::ffi::c_void);
+        assert_eq!(result, 0);
+    }
+}
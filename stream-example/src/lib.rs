@@ -23,6 +23,34 @@
 //! - Safe pointer validation using cimpl macros
 //! - Universal memory management with `cimpl_free()`
 //! - Standard error handling with error codes and messages
+//! - `CimplBufStream` wraps a stream with an internal buffer, for callers
+//!   doing lots of small reads/writes where the callback overhead dominates
+//! - Callbacks can report the real `std::io::ErrorKind`/OS error behind a
+//!   failure via `cimpl_stream_set_callback_error`, instead of every failure
+//!   collapsing to one generic I/O error code
+//! - Optional vectored (scatter/gather) read/write callbacks let a stream
+//!   service a whole iovec array in one call instead of one segment at a time
+//! - Every stream, buffered-stream and cursor operation is wrapped in
+//!   `catch_unwind` via `cimpl::call_with_result!`, so a Rust panic can never
+//!   unwind across the FFI boundary; the integer-returning ones report it with
+//!   a distinct `-2` sentinel (vs. `-1` for an ordinary error), and the panic
+//!   message is available through `cimpl_stream_last_error()`. The error
+//!   accessors and `cimpl_stream_set_callback_error` are not wrapped.
+//! - Errors carry structured detail beyond a bare code and message - a
+//!   `CimplErrorDomain`, an optional debug string, and the source location
+//!   that raised them - via `cimpl_stream_error_domain`/`_debug`/`_location`
+//! - `cimpl_stream_good`/`_eof`/`_bad` expose the classic good/eof/bad
+//!   stream-state model, so a read loop can tell a clean end-of-stream from
+//!   a real failure instead of treating every non-positive read the same way
+//! - `cimpl_stream_tell` reports the current position without moving it, and
+//!   `cimpl_stream_is_seekable`/`cimpl_stream_set_seekable` let a backend
+//!   that can't really seek (e.g. a pipe) say so up front
+//! - `cimpl_stream_new_with_close` builds a stream over a pluggable
+//!   read-only-capable backend, with an optional `close` callback guaranteed
+//!   to run exactly once when the stream is freed
+//! - `cimpl_stream_from_uri` opens a stream from a URI, validating and
+//!   dispatching on its scheme (currently `file://`); `cimpl_stream_uri_is_supported`
+//!   lets a caller probe scheme support without attempting to open
 //!
 //! ## Building
 //!
@@ -34,10 +62,12 @@
 //! - `target/release/libcimpl_stream.{a,so,dylib}` - The library
 //! - `include/cimpl_stream.h` - C header with full documentation
 
+use std::cell::Cell;
 use std::io::{Read, Seek, SeekFrom, Write};
 
 use cimpl::{
-    box_tracked, deref_mut_or_return_neg, ok_or_return, ptr_or_return_int, ptr_or_return_null, Error,
+    box_tracked, call_with_result, cstr_or_return, cstr_or_return_null, deref_mut_or_return,
+    deref_mut_or_return_neg, ok_or_return, ptr_or_return_int, ptr_or_return_null, Error,
 };
 
 // ============================================================================
@@ -53,7 +83,7 @@ use cimpl::{
 pub enum CimplStreamError {
     /// No error occurred
     Ok = 0,
-    
+
     // Core cimpl errors (1-99)
     /// A required parameter was NULL
     NullParameter = 1,
@@ -65,17 +95,213 @@ pub enum CimplStreamError {
     WrongHandleType = 4,
     /// Other unspecified error
     Other = 5,
-    
+
     // Stream-specific errors (100+)
     /// I/O operation failed (read, write, seek, or flush)
     IoOperation = 100,
     /// Invalid buffer pointer provided
     InvalidBuffer = 101,
+    /// `cimpl_stream_from_uri` was given a URI whose scheme isn't supported
+    UnsupportedProtocol = 102,
+}
+
+// ============================================================================
+// Callback Error Detail (ErrorKind / raw OS error preservation)
+// ============================================================================
+//
+// A callback that fails by returning -1 only tells us "it failed" - not
+// whether that was EOF, EINTR, EWOULDBLOCK, etc. A callback implementation
+// that wants to report that detail calls `cimpl_stream_set_callback_error`
+// just before returning its failure sentinel; the Read/Write/Seek impls
+// below pick it up and fold it into the `std::io::Error` they construct.
+//
+// The detail is carried as a single packed `i32`, in the spirit of std's own
+// bit-packed `io::Error` representation: bit 0 is a flag, and the remaining
+// bits are either a raw OS error code (flag set) or one of the
+// `CIMPL_IO_KIND_*` tags below (flag clear). This same packed value is what
+// ends up as `Error::last_code()`/`cimpl_stream_error_code()`, via
+// `Error::LibraryError`.
+
+/// Generic/unclassified I/O failure.
+pub const CIMPL_IO_KIND_OTHER: i32 = 0;
+/// The target of the operation does not exist.
+pub const CIMPL_IO_KIND_NOT_FOUND: i32 = 1;
+/// The operation lacked the necessary privileges.
+pub const CIMPL_IO_KIND_PERMISSION_DENIED: i32 = 2;
+/// The operation needs to block to complete, but the blocking operation was
+/// requested to not occur.
+pub const CIMPL_IO_KIND_WOULD_BLOCK: i32 = 3;
+/// An operation could not be completed because an "end of file" was reached
+/// prematurely.
+pub const CIMPL_IO_KIND_UNEXPECTED_EOF: i32 = 4;
+/// The operation was interrupted and should be retried.
+pub const CIMPL_IO_KIND_INTERRUPTED: i32 = 5;
+/// The connection was reset by the remote side.
+pub const CIMPL_IO_KIND_CONNECTION_RESET: i32 = 6;
+/// The operation failed because a pipe was closed.
+pub const CIMPL_IO_KIND_BROKEN_PIPE: i32 = 7;
+
+thread_local! {
+    /// Packed callback error detail set by `cimpl_stream_set_callback_error`,
+    /// consumed by the next `Read`/`Write`/`Seek` failure on this thread.
+    static CALLBACK_ERROR: Cell<Option<i32>> = Cell::new(None);
+}
+
+/// Reports richer detail for a callback failure than the bare -1 sentinel.
+///
+/// Call this immediately before returning -1 (or another failure value) from
+/// a read/write/seek/flush callback. `kind` should be one of the
+/// `CIMPL_IO_KIND_*` constants. If the platform gave a raw OS error number
+/// for the failure (e.g. `errno`), pass it as `os_code`; otherwise pass -1
+/// and `kind` alone is used.
+///
+/// # Example
+/// ```c
+/// if (native_read_failed_with(EINTR)) {
+///     cimpl_stream_set_callback_error(CIMPL_IO_KIND_INTERRUPTED, EINTR);
+///     return -1;
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn cimpl_stream_set_callback_error(kind: i32, os_code: i32) {
+    let packed = if os_code >= 0 { (os_code << 1) | 1 } else { kind << 1 };
+    CALLBACK_ERROR.with(|c| c.set(Some(packed)));
+}
+
+fn kind_tag_to_error_kind(tag: i32) -> std::io::ErrorKind {
+    use std::io::ErrorKind::*;
+    match tag {
+        CIMPL_IO_KIND_NOT_FOUND => NotFound,
+        CIMPL_IO_KIND_PERMISSION_DENIED => PermissionDenied,
+        CIMPL_IO_KIND_WOULD_BLOCK => WouldBlock,
+        CIMPL_IO_KIND_UNEXPECTED_EOF => UnexpectedEof,
+        CIMPL_IO_KIND_INTERRUPTED => Interrupted,
+        CIMPL_IO_KIND_CONNECTION_RESET => ConnectionReset,
+        CIMPL_IO_KIND_BROKEN_PIPE => BrokenPipe,
+        _ => Other,
+    }
+}
+
+fn error_kind_to_kind_tag(kind: std::io::ErrorKind) -> i32 {
+    use std::io::ErrorKind::*;
+    match kind {
+        NotFound => CIMPL_IO_KIND_NOT_FOUND,
+        PermissionDenied => CIMPL_IO_KIND_PERMISSION_DENIED,
+        WouldBlock => CIMPL_IO_KIND_WOULD_BLOCK,
+        UnexpectedEof => CIMPL_IO_KIND_UNEXPECTED_EOF,
+        Interrupted => CIMPL_IO_KIND_INTERRUPTED,
+        ConnectionReset => CIMPL_IO_KIND_CONNECTION_RESET,
+        BrokenPipe => CIMPL_IO_KIND_BROKEN_PIPE,
+        _ => CIMPL_IO_KIND_OTHER,
+    }
+}
+
+/// Builds the `std::io::Error` for a callback failure, consuming whatever
+/// detail `cimpl_stream_set_callback_error` left for this thread (if any).
+fn take_callback_io_error(message: &'static str) -> std::io::Error {
+    match CALLBACK_ERROR.with(|c| c.take()) {
+        Some(packed) if packed & 1 != 0 => std::io::Error::from_raw_os_error(packed >> 1),
+        Some(packed) => std::io::Error::new(kind_tag_to_error_kind(packed >> 1), message),
+        None => std::io::Error::new(std::io::ErrorKind::Other, message),
+    }
+}
+
+/// Packs a `std::io::Error` into the compact representation stored as
+/// `Error::last_code()`, preferring the raw OS error when present.
+fn pack_io_error(e: &std::io::Error) -> i32 {
+    match e.raw_os_error() {
+        Some(code) => (code << 1) | 1,
+        None => error_kind_to_kind_tag(e.kind()) << 1,
+    }
+}
+
+// Map std::io::Error to cimpl Error, preserving ErrorKind/OS error detail
+// via `pack_io_error` instead of collapsing every failure to one code, and
+// recording structured detail (see below) for every error it translates:
+// `CimplErrorDomain::Unsupported` for an `ErrorKind::Unsupported` failure,
+// `CimplErrorDomain::Io` for everything else.
+const ERROR_MAPPER: fn(&std::io::Error) -> (i32, &'static str) = |e| {
+    let domain = if e.kind() == std::io::ErrorKind::Unsupported {
+        CimplErrorDomain::Unsupported
+    } else {
+        CimplErrorDomain::Io
+    };
+    record_error_detail(domain, Some(format!("{:?}", e)), module_path!());
+    (pack_io_error(e), "IoError")
+};
+
+// ============================================================================
+// Structured Error Detail (domain / debug string / source location)
+// ============================================================================
+//
+// `cimpl_stream_error_code()` narrows every failure down to one packed
+// integer - enough to retry on WouldBlock, but not enough for a binding to
+// classify or log the failure without string-matching the message. This
+// thread-local holds the extra detail a GStreamer-style `ErrorMessage` would
+// carry: which *domain* the failure came from, an optional free-form debug
+// string, and the `module_path!()`/`file!()`/`line!()` that raised it. It is
+// set alongside (never instead of) the existing `Error::set_last()`, and
+// `cimpl_stream_clear_error()` clears both together.
+
+/// Broad classification of a structured stream error, mirroring the domains
+/// a GStreamer-style `ErrorMessage` would use.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CimplErrorDomain {
+    /// No structured detail is recorded for the current error.
+    None = 0,
+    /// Read/write/seek/flush failure against the underlying backend.
+    Io = 1,
+    /// The data read from the stream was malformed.
+    Parse = 2,
+    /// The requested operation or backend is not supported.
+    Unsupported = 3,
+    /// `cimpl_stream_from_uri` was given a URI with an unrecognized scheme.
+    UnsupportedProtocol = 4,
 }
 
-// Map std::io::Error to cimpl Error
-const ERROR_MAPPER: fn(&std::io::Error) -> (i32, &'static str) = 
-    |_e| (CimplStreamError::IoOperation as i32, "IoError");
+struct StreamErrorDetail {
+    domain: CimplErrorDomain,
+    debug: Option<String>,
+    module: &'static str,
+    file: &'static str,
+    line: u32,
+}
+
+thread_local! {
+    /// Structured detail for the last error raised via `error_msg!`/
+    /// `record_error_detail`, kept in lockstep with (but separate from)
+    /// `cimpl::Error`'s own thread-local last-error slot.
+    static STREAM_ERROR_DETAIL: std::cell::RefCell<Option<StreamErrorDetail>> =
+        std::cell::RefCell::new(None);
+}
+
+/// Records `domain`/`debug`/`module` plus the caller's source location as the
+/// structured detail for whatever error is about to be (or was just) set as
+/// the last error. Exists so `error_msg!` and `ERROR_MAPPER` can share the
+/// same bookkeeping; `module` should be the caller's own `module_path!()`.
+#[track_caller]
+fn record_error_detail(domain: CimplErrorDomain, debug: Option<String>, module: &'static str) {
+    let location = std::panic::Location::caller();
+    STREAM_ERROR_DETAIL.with(|d| {
+        *d.borrow_mut() =
+            Some(StreamErrorDetail { domain, debug, module, file: location.file(), line: location.line() });
+    });
+}
+
+/// Raises a structured error: records `domain`/`debug`/the call site (module,
+/// file and line) via `record_error_detail`, then sets
+/// `Error::LibraryError(code, msg)` as the last error. `debug` is an
+/// `Option<String>` - pass `None` when there is no detail beyond `msg`.
+///
+/// Call this immediately before returning the caller's failure sentinel, the
+/// same as `Error::set_last()` would be used directly.
+macro_rules! error_msg {
+    ($domain:expr, $code:expr, $msg:expr, $debug:expr) => {{
+        record_error_detail($domain, $debug, module_path!());
+        Error::LibraryError($code, $msg).set_last();
+    }};
+}
 
 // ============================================================================
 // Stream Context and Callbacks
@@ -161,6 +387,57 @@ pub type CimplWriteCallback = unsafe extern "C" fn(
 /// - -1 on error
 pub type CimplFlushCallback = unsafe extern "C" fn(context: *mut CimplStreamContext) -> i32;
 
+/// Close callback: releases the caller's storage backing a stream.
+///
+/// Invoked exactly once, when the `CimplStream` is freed - never called
+/// directly by any `cimpl_stream_*` function.
+///
+/// # Parameters
+/// - `context`: The stream context provided when creating the stream
+pub type CimplCloseCallback = unsafe extern "C" fn(context: *mut CimplStreamContext);
+
+/// A single scatter/gather buffer segment, mirroring Rust's
+/// `IoSlice`/`IoSliceMut`. Used for both directions: vectored reads fill
+/// `ptr[0..len)`, vectored writes read from it.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct CimplIoSlice {
+    pub ptr: *mut u8,
+    pub len: usize,
+}
+
+/// Vectored read callback: fills multiple buffer segments in one call.
+///
+/// # Parameters
+/// - `context`: The stream context provided when creating the stream
+/// - `iovs`: Array of segments to fill
+/// - `n`: Number of segments in `iovs`
+///
+/// # Returns
+/// - Total number of bytes read across all segments (>= 0) on success
+/// - -1 on error
+pub type CimplReadVectoredCallback = unsafe extern "C" fn(
+    context: *mut CimplStreamContext,
+    iovs: *mut CimplIoSlice,
+    n: usize,
+) -> isize;
+
+/// Vectored write callback: writes multiple buffer segments in one call.
+///
+/// # Parameters
+/// - `context`: The stream context provided when creating the stream
+/// - `iovs`: Array of segments to write
+/// - `n`: Number of segments in `iovs`
+///
+/// # Returns
+/// - Total number of bytes written across all segments (>= 0) on success
+/// - -1 on error
+pub type CimplWriteVectoredCallback = unsafe extern "C" fn(
+    context: *mut CimplStreamContext,
+    iovs: *const CimplIoSlice,
+    n: usize,
+) -> isize;
+
 // ============================================================================
 // Stream Structure
 // ============================================================================
@@ -174,8 +451,49 @@ pub struct CimplStream {
     context: *mut CimplStreamContext,
     reader: CimplReadCallback,
     seeker: CimplSeekCallback,
-    writer: CimplWriteCallback,
-    flusher: CimplFlushCallback,
+    writer: Option<CimplWriteCallback>,
+    flusher: Option<CimplFlushCallback>,
+    closer: Option<CimplCloseCallback>,
+    read_vectored: Option<CimplReadVectoredCallback>,
+    write_vectored: Option<CimplWriteVectoredCallback>,
+    state: Cell<StreamState>,
+    seekable: Cell<bool>,
+}
+
+/// Per-stream state, mirroring the classic C++ `good()`/`eof()`/`bad()`
+/// stream-state model instead of forcing callers to re-derive it from return
+/// codes alone.
+///
+/// `Bad` is sticky: once a read/write/seek/flush fails, the stream reports
+/// `bad()` for every later predicate check, even across an intervening
+/// successful call, until a successful `cimpl_stream_seek()` clears it (the
+/// same recovery convention C's `fseek()`/`rewind()` use to clear `feof()`/
+/// `ferror()`). `Eof` is cleared by the next successful seek or non-empty
+/// read, since it just reflects whether the *last* read ran out of data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamState {
+    /// No error and no end-of-stream condition since the last clearing event.
+    Good,
+    /// The last read reached a clean end-of-stream; not itself an error.
+    Eof,
+    /// A read/write/seek/flush failed; sticky until a successful seek.
+    Bad,
+}
+
+impl CimplStream {
+    /// Records that an operation completed; `eof` is whether it was a read
+    /// that found no more data to read. A no-op if the stream is already
+    /// `Bad` - only a successful seek can clear that.
+    fn note_success(&self, eof: bool) {
+        if self.state.get() != StreamState::Bad {
+            self.state.set(if eof { StreamState::Eof } else { StreamState::Good });
+        }
+    }
+
+    /// Records that an operation failed, entering the sticky `Bad` state.
+    fn note_failure(&self) {
+        self.state.set(StreamState::Bad);
+    }
 }
 
 // ============================================================================
@@ -224,17 +542,248 @@ pub extern "C" fn cimpl_stream_new(
     writer: CimplWriteCallback,
     flusher: CimplFlushCallback,
 ) -> *mut CimplStream {
-    ptr_or_return_null!(context);
+    call_with_result!(
+        {
+            ptr_or_return_null!(context);
+
+            let stream = CimplStream {
+                context,
+                reader,
+                seeker,
+                writer: Some(writer),
+                flusher: Some(flusher),
+                closer: None,
+                read_vectored: None,
+                write_vectored: None,
+                state: Cell::new(StreamState::Good),
+                seekable: Cell::new(true),
+            };
 
-    let stream = CimplStream {
-        context,
-        reader,
-        seeker,
-        writer,
-        flusher,
-    };
+            box_tracked!(stream)
+        },
+        std::ptr::null_mut()
+    )
+}
 
-    box_tracked!(stream)
+/// Creates a new stream from C callbacks, for a pluggable backend (a custom
+/// in-memory buffer, a network socket, a memory-mapped region, ...) that may
+/// not support writing and needs to release its own storage when the stream
+/// is freed.
+///
+/// Unlike `cimpl_stream_new`, `writer` and `flusher` are optional: pass NULL
+/// for `writer` to make the stream read-only (writes fail with a structured
+/// `CimplErrorDomain::Unsupported` error); `flusher` may be NULL if there is
+/// nothing to flush. `closer`, if not NULL, is invoked exactly once - when
+/// the stream is freed with `cimpl_free()` - to release `context`.
+///
+/// # Parameters
+/// - `context`: Opaque pointer to caller's stream context (passed to all callbacks)
+/// - `reader`: Callback function for reading data
+/// - `seeker`: Callback function for seeking
+/// - `writer`: Optional callback function for writing data
+/// - `flusher`: Optional callback function for flushing
+/// - `closer`: Optional callback invoked once when the stream is freed
+///
+/// # Returns
+/// - Pointer to the new stream on success
+/// - NULL on error (check `cimpl_stream_last_error()` for details)
+///
+/// # Safety
+/// - The context pointer must remain valid until `closer` runs (or, if
+///   `closer` is NULL, for the lifetime of the stream)
+/// - All callback functions must remain valid for the lifetime of the stream
+/// - The returned stream must be freed with `cimpl_free()` when done
+#[no_mangle]
+pub extern "C" fn cimpl_stream_new_with_close(
+    context: *mut CimplStreamContext,
+    reader: CimplReadCallback,
+    seeker: CimplSeekCallback,
+    writer: Option<CimplWriteCallback>,
+    flusher: Option<CimplFlushCallback>,
+    closer: Option<CimplCloseCallback>,
+) -> *mut CimplStream {
+    call_with_result!(
+        {
+            ptr_or_return_null!(context);
+
+            let stream = CimplStream {
+                context,
+                reader,
+                seeker,
+                writer,
+                flusher,
+                closer,
+                read_vectored: None,
+                write_vectored: None,
+                state: Cell::new(StreamState::Good),
+                seekable: Cell::new(true),
+            };
+
+            box_tracked!(stream)
+        },
+        std::ptr::null_mut()
+    )
+}
+
+/// Attaches optional vectored (scatter/gather) read/write callbacks to an
+/// existing stream, so `cimpl_stream_read_vectored`/`cimpl_stream_write_vectored`
+/// can forward a whole iovec array in one callback instead of iterating the
+/// slices through the scalar `reader`/`writer`. Pass NULL for either to leave
+/// (or reset) that direction on the scalar fallback path.
+///
+/// # Returns
+/// - 0 on success
+/// - -1 on error
+#[no_mangle]
+pub extern "C" fn cimpl_stream_set_vectored_callbacks(
+    stream: *mut CimplStream,
+    read_vectored: Option<CimplReadVectoredCallback>,
+    write_vectored: Option<CimplWriteVectoredCallback>,
+) -> i32 {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+            s.read_vectored = read_vectored;
+            s.write_vectored = write_vectored;
+            0
+        },
+        -2
+    )
+}
+
+// ============================================================================
+// URI-based Stream Construction
+// ============================================================================
+
+/// Backing storage for `cimpl_stream_from_uri`'s `file://` backend: an open
+/// `std::fs::File`, driven through the ordinary `CimplStream` callback
+/// interface like `CursorBuffer`, but released via `closer` (see
+/// `cimpl_stream_new_with_close`) instead of requiring a dedicated teardown
+/// call.
+struct FileBackend {
+    file: std::fs::File,
+}
+
+impl FileBackend {
+    unsafe extern "C" fn read_callback(
+        ctx: *mut CimplStreamContext,
+        data: *mut u8,
+        len: usize,
+    ) -> isize {
+        let backend = &mut *(ctx as *mut FileBackend);
+        let buf = std::slice::from_raw_parts_mut(data, len);
+        match backend.file.read(buf) {
+            Ok(n) => n as isize,
+            Err(_) => -1,
+        }
+    }
+
+    unsafe extern "C" fn seek_callback(
+        ctx: *mut CimplStreamContext,
+        offset: i64,
+        mode: CimplSeekMode,
+    ) -> i64 {
+        let backend = &mut *(ctx as *mut FileBackend);
+        let seek_from = match mode {
+            CimplSeekMode::Start => SeekFrom::Start(offset.max(0) as u64),
+            CimplSeekMode::Current => SeekFrom::Current(offset),
+            CimplSeekMode::End => SeekFrom::End(offset),
+        };
+        match backend.file.seek(seek_from) {
+            Ok(pos) => pos as i64,
+            Err(_) => -1,
+        }
+    }
+
+    unsafe extern "C" fn close_callback(ctx: *mut CimplStreamContext) {
+        drop(Box::from_raw(ctx as *mut FileBackend));
+    }
+}
+
+/// Splits `uri` into its scheme and the remainder after `://`, e.g.
+/// `"file:///tmp/x"` -> `Some(("file", "/tmp/x"))`. Returns `None` if there
+/// is no `://` separator.
+fn split_uri_scheme(uri: &str) -> Option<(&str, &str)> {
+    uri.split_once("://")
+}
+
+/// Reports whether `cimpl_stream_from_uri(uri)` recognizes `uri`'s scheme.
+/// Shared by `cimpl_stream_from_uri` (to decide whether to even try) and
+/// `cimpl_stream_uri_is_supported` (so callers can probe without trying).
+fn uri_is_supported(uri: &str) -> bool {
+    matches!(split_uri_scheme(uri), Some(("file", _)))
+}
+
+/// Opens a stream for the given URI, validating and dispatching on its
+/// scheme.
+///
+/// Currently only `file://` is supported, opened read-only against the
+/// local filesystem. Call `cimpl_stream_uri_is_supported()` first to probe
+/// support without attempting to open.
+///
+/// # Parameters
+/// - `uri`: Null-terminated URI string, e.g. `"file:///tmp/data.bin"`
+///
+/// # Returns
+/// - Pointer to the new stream on success
+/// - NULL on error - an unrecognized scheme sets a
+///   `CimplErrorDomain::UnsupportedProtocol` error naming `uri`; a missing
+///   or unreadable file sets the usual `CimplErrorDomain::Io` error. Either
+///   way, check `cimpl_stream_last_error()`.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_from_uri(uri: *const std::os::raw::c_char) -> *mut CimplStream {
+    call_with_result!(
+        {
+            let uri = cstr_or_return_null!(uri);
+
+            match split_uri_scheme(&uri) {
+                Some(("file", path)) => match std::fs::File::open(path) {
+                    Ok(file) => {
+                        let context = Box::into_raw(Box::new(FileBackend { file }))
+                            as *mut CimplStreamContext;
+                        cimpl_stream_new_with_close(
+                            context,
+                            FileBackend::read_callback,
+                            FileBackend::seek_callback,
+                            None,
+                            None,
+                            Some(FileBackend::close_callback),
+                        )
+                    }
+                    Err(e) => {
+                        Error::from_mapper(e, ERROR_MAPPER).set_last();
+                        std::ptr::null_mut()
+                    }
+                },
+                _ => {
+                    error_msg!(
+                        CimplErrorDomain::UnsupportedProtocol,
+                        CimplStreamError::UnsupportedProtocol as i32,
+                        format!("unsupported URI scheme: {uri}"),
+                        None
+                    );
+                    std::ptr::null_mut()
+                }
+            }
+        },
+        std::ptr::null_mut()
+    )
+}
+
+/// Reports whether `cimpl_stream_from_uri(uri)` would recognize `uri`'s
+/// scheme, without attempting to open it.
+///
+/// # Parameters
+/// - `uri`: Null-terminated URI string
+#[no_mangle]
+pub extern "C" fn cimpl_stream_uri_is_supported(uri: *const std::os::raw::c_char) -> bool {
+    call_with_result!(
+        {
+            let uri = cstr_or_return!(uri, false);
+            uri_is_supported(&uri)
+        },
+        false
+    )
 }
 
 // ============================================================================
@@ -249,8 +798,10 @@ pub extern "C" fn cimpl_stream_new(
 /// - `len`: Number of bytes to read
 ///
 /// # Returns
-/// - Number of bytes actually read (>= 0) on success
-/// - -1 on error
+/// - Number of bytes actually read (> 0) on success
+/// - 0 on a clean end-of-stream (check `cimpl_stream_eof()` to confirm - it's
+///   not an error)
+/// - -1 on error (check `cimpl_stream_bad()`/`cimpl_stream_last_error()`)
 ///
 /// # Example
 /// ```c
@@ -258,6 +809,8 @@ pub extern "C" fn cimpl_stream_new(
 /// isize bytes_read = cimpl_stream_read(stream, buffer, sizeof(buffer));
 /// if (bytes_read < 0) {
 ///     fprintf(stderr, "Read error\n");
+/// } else if (bytes_read == 0 && cimpl_stream_eof(stream)) {
+///     // Clean end of stream.
 /// }
 /// ```
 #[no_mangle]
@@ -266,13 +819,18 @@ pub extern "C" fn cimpl_stream_read(
     buffer: *mut u8,
     len: usize,
 ) -> isize {
-    let s = deref_mut_or_return_neg!(stream, CimplStream);
-    ptr_or_return_int!(buffer);
-
-    // Create a safe slice from the raw pointer
-    let buf = unsafe { std::slice::from_raw_parts_mut(buffer, len) };
-
-    ok_or_return!(s.read(buf), |bytes_read| bytes_read as isize, -1)
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+            ptr_or_return_int!(buffer);
+
+            // Create a safe slice from the raw pointer
+            let buf = unsafe { std::slice::from_raw_parts_mut(buffer, len) };
+
+            ok_or_return!(s.read(buf), |bytes_read| bytes_read as isize, -1, ERROR_MAPPER)
+        },
+        -2
+    )
 }
 
 /// Seeks to a position in the stream.
@@ -303,15 +861,78 @@ pub extern "C" fn cimpl_stream_seek(
     offset: i64,
     mode: CimplSeekMode,
 ) -> i64 {
-    let s = deref_mut_or_return_neg!(stream, CimplStream);
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+
+            let seek_from = match mode {
+                CimplSeekMode::Start => SeekFrom::Start(offset as u64),
+                CimplSeekMode::Current => SeekFrom::Current(offset),
+                CimplSeekMode::End => SeekFrom::End(offset),
+            };
 
-    let seek_from = match mode {
-        CimplSeekMode::Start => SeekFrom::Start(offset as u64),
-        CimplSeekMode::Current => SeekFrom::Current(offset),
-        CimplSeekMode::End => SeekFrom::End(offset),
-    };
+            ok_or_return!(s.seek(seek_from), |pos| pos as i64, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+/// Gets the current position in the stream, without changing it.
+///
+/// Equivalent to `cimpl_stream_seek(stream, 0, CIMPL_SEEK_CURRENT)`.
+///
+/// # Returns
+/// - Current position (>= 0) on success
+/// - -1 on error
+#[no_mangle]
+pub extern "C" fn cimpl_stream_tell(stream: *mut CimplStream) -> i64 {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+            ok_or_return!(s.seek(SeekFrom::Current(0)), |pos| pos as i64, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+/// Reports whether the stream supports seeking.
+///
+/// Defaults to `true` - every stream is constructed with a seek callback -
+/// until a caller marks it otherwise with `cimpl_stream_set_seekable`, for
+/// backends (e.g. a pipe or a network socket) whose seek callback can only
+/// fail.
+///
+/// # Returns
+/// `true` if the stream is seekable; `false` (and an invalid/NULL `stream`)
+/// otherwise.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_is_seekable(stream: *mut CimplStream) -> bool {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return!(stream, CimplStream, false);
+            s.seekable.get()
+        },
+        false
+    )
+}
 
-    ok_or_return!(s.seek(seek_from), |pos| pos as i64, -1)
+/// Marks whether a stream supports seeking, so `cimpl_stream_is_seekable()`
+/// can report it up front instead of callers discovering it by trying (and
+/// failing) a real seek.
+///
+/// # Returns
+/// - 0 on success
+/// - -1 on error
+#[no_mangle]
+pub extern "C" fn cimpl_stream_set_seekable(stream: *mut CimplStream, seekable: bool) -> i32 {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+            s.seekable.set(seekable);
+            0
+        },
+        -2
+    )
 }
 
 /// Writes data to the stream.
@@ -339,12 +960,17 @@ pub extern "C" fn cimpl_stream_write(
     data: *const u8,
     len: usize,
 ) -> isize {
-    let s = deref_mut_or_return_neg!(stream, CimplStream);
-    ptr_or_return_int!(data);
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+            ptr_or_return_int!(data);
 
-    let buf = unsafe { std::slice::from_raw_parts(data, len) };
+            let buf = unsafe { std::slice::from_raw_parts(data, len) };
 
-    ok_or_return!(s.write(buf), |bytes_written| bytes_written as isize, -1)
+            ok_or_return!(s.write(buf), |bytes_written| bytes_written as isize, -1, ERROR_MAPPER)
+        },
+        -2
+    )
 }
 
 /// Flushes the stream, ensuring all buffered data is written.
@@ -364,9 +990,180 @@ pub extern "C" fn cimpl_stream_write(
 /// ```
 #[no_mangle]
 pub extern "C" fn cimpl_stream_flush(stream: *mut CimplStream) -> i32 {
-    let s = deref_mut_or_return_neg!(stream, CimplStream);
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+
+            ok_or_return!(s.flush(), |_| 0, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+// ============================================================================
+// Stream State (good / eof / bad)
+// ============================================================================
+
+/// Reports whether the stream is in the default, error-free state: no
+/// operation has failed and the last read has not reached end-of-stream.
+///
+/// # Returns
+/// `true` if neither `cimpl_stream_eof()` nor `cimpl_stream_bad()` would be
+/// true; `false` (and an invalid/NULL `stream`) otherwise.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_good(stream: *mut CimplStream) -> bool {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return!(stream, CimplStream, false);
+            s.state.get() == StreamState::Good
+        },
+        false
+    )
+}
+
+/// Reports whether the last read on the stream reached a clean end-of-stream.
+///
+/// This is cleared by the next successful seek, or by a subsequent read that
+/// finds more data; it is not itself an error (see `cimpl_stream_bad()`).
+///
+/// # Returns
+/// `true` if the stream last hit end-of-stream; `false` (and an
+/// invalid/NULL `stream`) otherwise.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_eof(stream: *mut CimplStream) -> bool {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return!(stream, CimplStream, false);
+            s.state.get() == StreamState::Eof
+        },
+        false
+    )
+}
+
+/// Reports whether a read/write/seek/flush on the stream has failed.
+///
+/// This is sticky: it stays `true` across later calls, even successful ones,
+/// until a `cimpl_stream_seek()` call succeeds - the same recovery convention
+/// C's `fseek()`/`rewind()` use to clear `ferror()`.
+///
+/// # Returns
+/// `true` if the stream is in the failed state; `false` (and an
+/// invalid/NULL `stream`) otherwise.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_bad(stream: *mut CimplStream) -> bool {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return!(stream, CimplStream, false);
+            s.state.get() == StreamState::Bad
+        },
+        false
+    )
+}
+
+/// Performs a scatter read: fills as many of the supplied buffer segments as
+/// a single underlying read can satisfy. If the stream was given a vectored
+/// read callback (see `cimpl_stream_set_vectored_callbacks`), the whole
+/// `iovs` array is forwarded to it in one call; otherwise this falls back to
+/// reading into the first non-empty segment.
+///
+/// # Parameters
+/// - `stream`: The stream to read from
+/// - `iovs`: Array of buffer segments to fill (must not be NULL)
+/// - `n`: Number of segments in `iovs`
+///
+/// # Returns
+/// - Number of bytes actually read (>= 0) on success
+/// - -1 on error
+#[no_mangle]
+pub extern "C" fn cimpl_stream_read_vectored(
+    stream: *mut CimplStream,
+    iovs: *mut CimplIoSlice,
+    n: usize,
+) -> isize {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+            ptr_or_return_int!(iovs);
+
+            let slices = unsafe { std::slice::from_raw_parts(iovs, n) };
+            let mut bufs: Vec<std::io::IoSliceMut> = slices
+                .iter()
+                .map(|slice| unsafe {
+                    std::io::IoSliceMut::new(std::slice::from_raw_parts_mut(slice.ptr, slice.len))
+                })
+                .collect();
+
+            ok_or_return!(s.read_vectored(&mut bufs), |bytes_read| bytes_read as isize, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+/// Performs a gather write: writes as many of the supplied buffer segments
+/// as a single underlying write can accept. If the stream was given a
+/// vectored write callback (see `cimpl_stream_set_vectored_callbacks`), the
+/// whole `iovs` array is forwarded to it in one call; otherwise this falls
+/// back to writing the first non-empty segment.
+///
+/// # Parameters
+/// - `stream`: The stream to write to
+/// - `iovs`: Array of buffer segments to write (must not be NULL)
+/// - `n`: Number of segments in `iovs`
+///
+/// # Returns
+/// - Number of bytes actually written (>= 0) on success
+/// - -1 on error
+#[no_mangle]
+pub extern "C" fn cimpl_stream_write_vectored(
+    stream: *mut CimplStream,
+    iovs: *const CimplIoSlice,
+    n: usize,
+) -> isize {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+            ptr_or_return_int!(iovs);
+
+            let slices = unsafe { std::slice::from_raw_parts(iovs, n) };
+            let bufs: Vec<std::io::IoSlice> = slices
+                .iter()
+                .map(|slice| unsafe {
+                    std::io::IoSlice::new(std::slice::from_raw_parts(slice.ptr, slice.len))
+                })
+                .collect();
+
+            ok_or_return!(s.write_vectored(&bufs), |bytes_written| bytes_written as isize, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+/// Reports whether the stream has a vectored read callback attached, so
+/// callers can pick between `cimpl_stream_read_vectored` and repeated
+/// `cimpl_stream_read` calls.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_is_read_vectored(stream: *mut CimplStream) -> bool {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return!(stream, CimplStream, false);
+            s.is_read_vectored()
+        },
+        false
+    )
+}
 
-    ok_or_return!(s.flush(), |_| 0, -1)
+/// Reports whether the stream has a vectored write callback attached, so
+/// callers can pick between `cimpl_stream_write_vectored` and repeated
+/// `cimpl_stream_write` calls.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_is_write_vectored(stream: *mut CimplStream) -> bool {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return!(stream, CimplStream, false);
+            s.is_write_vectored()
+        },
+        false
+    )
 }
 
 // ============================================================================
@@ -385,14 +1182,48 @@ impl Read for CimplStream {
         let bytes_read = unsafe { (self.reader)(self.context, buf.as_mut_ptr(), buf.len()) };
 
         if bytes_read < 0 {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Read callback returned error",
-            ));
+            self.note_failure();
+            return Err(take_callback_io_error("Read callback returned error"));
+        }
+
+        self.note_success(bytes_read == 0 && !buf.is_empty());
+        Ok(bytes_read as usize)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [std::io::IoSliceMut<'_>]) -> std::io::Result<usize> {
+        let Some(read_vectored) = self.read_vectored else {
+            // No vectored callback was supplied; fall back to reading into the
+            // first non-empty segment, matching the scalar reader's contract.
+            let buf = bufs
+                .iter_mut()
+                .find(|b| !b.is_empty())
+                .map_or(&mut [][..], |b| &mut **b);
+            return self.read(buf);
+        };
+
+        let mut iovs: Vec<CimplIoSlice> = bufs
+            .iter_mut()
+            .map(|b| CimplIoSlice {
+                ptr: b.as_mut_ptr(),
+                len: b.len(),
+            })
+            .collect();
+
+        let bytes_read =
+            unsafe { read_vectored(self.context, iovs.as_mut_ptr(), iovs.len()) };
+
+        if bytes_read < 0 {
+            self.note_failure();
+            return Err(take_callback_io_error("Vectored read callback returned error"));
         }
 
+        self.note_success(bytes_read == 0 && iovs.iter().any(|iov| iov.len > 0));
         Ok(bytes_read as usize)
     }
+
+    fn is_read_vectored(&self) -> bool {
+        self.read_vectored.is_some()
+    }
 }
 
 impl Seek for CimplStream {
@@ -406,12 +1237,13 @@ impl Seek for CimplStream {
         let new_pos = unsafe { (self.seeker)(self.context, offset, mode) };
 
         if new_pos < 0 {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Seek callback returned error",
-            ));
+            self.note_failure();
+            return Err(take_callback_io_error("Seek callback returned error"));
         }
 
+        // A successful seek is the recovery path for a prior `bad`/`eof`
+        // state, mirroring C's `fseek()`/`rewind()` clearing `feof()`/`ferror()`.
+        self.state.set(StreamState::Good);
         Ok(new_pos as u64)
     }
 }
@@ -425,30 +1257,662 @@ impl Write for CimplStream {
             ));
         }
 
-        let bytes_written = unsafe { (self.writer)(self.context, buf.as_ptr(), buf.len()) };
-
-        if bytes_written < 0 {
+        let Some(writer) = self.writer else {
+            self.note_failure();
             return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Write callback returned error",
+                std::io::ErrorKind::Unsupported,
+                "stream does not support writing",
             ));
+        };
+
+        let bytes_written = unsafe { writer(self.context, buf.as_ptr(), buf.len()) };
+
+        if bytes_written < 0 {
+            self.note_failure();
+            return Err(take_callback_io_error("Write callback returned error"));
         }
 
+        self.note_success(false);
         Ok(bytes_written as usize)
     }
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        let result = unsafe { (self.flusher)(self.context) };
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+        let Some(write_vectored) = self.write_vectored else {
+            // No vectored callback was supplied; fall back to writing the
+            // first non-empty segment, matching the scalar writer's contract.
+            let buf = bufs.iter().find(|b| !b.is_empty()).map_or(&[][..], |b| &**b);
+            return self.write(buf);
+        };
 
-        if result != 0 {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Flush callback returned error",
-            ));
+        let iovs: Vec<CimplIoSlice> = bufs
+            .iter()
+            .map(|b| CimplIoSlice {
+                ptr: b.as_ptr() as *mut u8,
+                len: b.len(),
+            })
+            .collect();
+
+        let bytes_written =
+            unsafe { write_vectored(self.context, iovs.as_ptr(), iovs.len()) };
+
+        if bytes_written < 0 {
+            self.note_failure();
+            return Err(take_callback_io_error("Vectored write callback returned error"));
+        }
+
+        self.note_success(false);
+        Ok(bytes_written as usize)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.write_vectored.is_some()
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let Some(flusher) = self.flusher else {
+            return Ok(());
+        };
+
+        let result = unsafe { flusher(self.context) };
+
+        if result != 0 {
+            self.note_failure();
+            return Err(take_callback_io_error("Flush callback returned error"));
         }
 
+        self.note_success(false);
+        Ok(())
+    }
+}
+
+impl Drop for CimplStream {
+    fn drop(&mut self) {
+        if let Some(closer) = self.closer {
+            unsafe { closer(self.context) };
+        }
+    }
+}
+
+// ============================================================================
+// Buffered Stream
+// ============================================================================
+
+/// A buffered wrapper around a [`CimplStream`], mirroring std's
+/// `BufReader`/`BufWriter`.
+///
+/// Reads are served out of an internal buffer that's refilled with a single
+/// `reader` callback once drained; writes accumulate into an internal buffer
+/// that's flushed through the `writer` callback once full (or explicitly, via
+/// `cimpl_bufstream_flush`). This amortizes the cost of the C callback round trip
+/// for byte-at-a-time access patterns.
+///
+/// The wrapped stream is not owned: freeing a `CimplBufStream` with
+/// `cimpl_free()` does not free the inner `CimplStream`, which the caller must
+/// free separately.
+pub struct CimplBufStream {
+    inner: *mut CimplStream,
+    read_buf: Vec<u8>,
+    read_pos: usize,
+    read_cap: usize,
+    write_buf: Vec<u8>,
+    write_capacity: usize,
+}
+
+impl CimplBufStream {
+    fn inner(&mut self) -> &mut CimplStream {
+        unsafe { &mut *self.inner }
+    }
+
+    fn flush_write_buf(&mut self) -> std::io::Result<()> {
+        if !self.write_buf.is_empty() {
+            self.inner().write_all(&self.write_buf)?;
+            self.write_buf.clear();
+        }
         Ok(())
     }
+
+    /// Refills the read buffer from the inner stream, transparently retrying
+    /// on `Interrupted` rather than surfacing it as a failure - matching
+    /// `std::io::BufReader::fill_buf`.
+    fn refill(&mut self) -> std::io::Result<()> {
+        loop {
+            match self.inner().read(&mut self.read_buf) {
+                Ok(n) => {
+                    self.read_cap = n;
+                    self.read_pos = 0;
+                    return Ok(());
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Reads bytes up to and including `delimiter`, refilling the internal
+    /// read buffer as needed. Stops at the delimiter or once a refill
+    /// returns zero bytes (EOF); mirrors `std::io::BufRead::read_until`.
+    fn read_until(&mut self, delimiter: u8) -> std::io::Result<Vec<u8>> {
+        let mut result = Vec::new();
+        loop {
+            if self.read_pos >= self.read_cap {
+                self.refill()?;
+                if self.read_cap == 0 {
+                    break;
+                }
+            }
+
+            let available = &self.read_buf[self.read_pos..self.read_cap];
+            match available.iter().position(|&b| b == delimiter) {
+                Some(i) => {
+                    result.extend_from_slice(&available[..=i]);
+                    self.read_pos += i + 1;
+                    break;
+                }
+                None => {
+                    result.extend_from_slice(available);
+                    self.read_pos = self.read_cap;
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Read for CimplBufStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.read_pos >= self.read_cap {
+            // A read at least as large as our buffer would just be copied
+            // through it, so bypass the buffer and read straight into `buf`.
+            if buf.len() >= self.read_buf.len() {
+                return self.inner().read(buf);
+            }
+            self.refill()?;
+        }
+
+        let available = &self.read_buf[self.read_pos..self.read_cap];
+        let to_copy = available.len().min(buf.len());
+        buf[..to_copy].copy_from_slice(&available[..to_copy]);
+        self.read_pos += to_copy;
+        Ok(to_copy)
+    }
+}
+
+impl Seek for CimplBufStream {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.flush_write_buf()?;
+        self.read_pos = 0;
+        self.read_cap = 0;
+        self.inner().seek(pos)
+    }
+}
+
+impl Write for CimplBufStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.write_buf.len() + buf.len() > self.write_capacity {
+            self.flush_write_buf()?;
+        }
+
+        // A write at least as large as our buffer would just be copied
+        // through it, so write it straight to the inner stream.
+        if buf.len() >= self.write_capacity {
+            return self.inner().write(buf);
+        }
+
+        self.write_buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flush_write_buf()?;
+        self.inner().flush()
+    }
+}
+
+impl Drop for CimplBufStream {
+    fn drop(&mut self) {
+        // Best-effort, like std's BufWriter: a drop can't report an error.
+        let _ = self.flush_write_buf();
+    }
+}
+
+/// Creates a buffered wrapper around an existing stream.
+///
+/// # Parameters
+/// - `inner`: The stream to wrap. Must outlive the returned buffered stream.
+/// - `read_capacity`: Size in bytes of the internal read buffer.
+/// - `write_capacity`: Size in bytes of the internal write buffer.
+///
+/// # Returns
+/// - Pointer to the new buffered stream on success
+/// - NULL on error (check `cimpl_stream_last_error()` for details)
+///
+/// # Safety
+/// - `inner` must remain valid for the lifetime of the buffered stream
+/// - The returned stream must be freed with `cimpl_free()` when done; this
+///   does NOT free `inner`, which the caller still owns
+#[no_mangle]
+pub extern "C" fn cimpl_bufstream_new(
+    inner: *mut CimplStream,
+    read_capacity: usize,
+    write_capacity: usize,
+) -> *mut CimplBufStream {
+    call_with_result!(
+        {
+            ptr_or_return_null!(inner);
+
+            let buf_stream = CimplBufStream {
+                inner,
+                read_buf: vec![0u8; read_capacity],
+                read_pos: 0,
+                read_cap: 0,
+                write_buf: Vec::with_capacity(write_capacity),
+                write_capacity,
+            };
+
+            box_tracked!(buf_stream)
+        },
+        std::ptr::null_mut()
+    )
+}
+
+/// Reads data from a buffered stream. See `cimpl_stream_read`.
+#[no_mangle]
+pub extern "C" fn cimpl_bufstream_read(
+    stream: *mut CimplBufStream,
+    buffer: *mut u8,
+    len: usize,
+) -> isize {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplBufStream);
+            ptr_or_return_int!(buffer);
+
+            let buf = unsafe { std::slice::from_raw_parts_mut(buffer, len) };
+
+            ok_or_return!(s.read(buf), |bytes_read| bytes_read as isize, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+/// Seeks to a position in a buffered stream. See `cimpl_stream_seek`.
+#[no_mangle]
+pub extern "C" fn cimpl_bufstream_seek(
+    stream: *mut CimplBufStream,
+    offset: i64,
+    mode: CimplSeekMode,
+) -> i64 {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplBufStream);
+
+            let seek_from = match mode {
+                CimplSeekMode::Start => SeekFrom::Start(offset as u64),
+                CimplSeekMode::Current => SeekFrom::Current(offset),
+                CimplSeekMode::End => SeekFrom::End(offset),
+            };
+
+            ok_or_return!(s.seek(seek_from), |pos| pos as i64, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+/// Writes data to a buffered stream. See `cimpl_stream_write`.
+#[no_mangle]
+pub extern "C" fn cimpl_bufstream_write(
+    stream: *mut CimplBufStream,
+    data: *const u8,
+    len: usize,
+) -> isize {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplBufStream);
+            ptr_or_return_int!(data);
+
+            let buf = unsafe { std::slice::from_raw_parts(data, len) };
+
+            ok_or_return!(s.write(buf), |bytes_written| bytes_written as isize, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+/// Flushes a buffered stream's pending writes through to the inner stream.
+/// See `cimpl_stream_flush`.
+#[no_mangle]
+pub extern "C" fn cimpl_bufstream_flush(stream: *mut CimplBufStream) -> i32 {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplBufStream);
+
+            ok_or_return!(s.flush(), |_| 0, -1, ERROR_MAPPER)
+        },
+        -2
+    )
+}
+
+// ============================================================================
+// Line-Oriented Reading
+// ============================================================================
+
+/// Reads bytes from a buffered stream up to and including `delimiter`.
+///
+/// # Parameters
+/// - `stream`: The buffered stream to read from
+/// - `delimiter`: The byte value to stop at (inclusive)
+/// - `out`: Receives a newly allocated buffer of the bytes read
+/// - `out_len`: Receives the number of bytes in `out`
+///
+/// # Returns
+/// - 0 on success - note that `*out_len == 0` means a clean EOF with nothing
+///   left to read, while a positive `*out_len` with no trailing `delimiter`
+///   means a final partial chunk before EOF
+/// - -1 on error
+///
+/// # Memory Management
+/// `*out` must be freed with `cimpl_free()`.
+///
+/// # Example
+/// ```c
+/// uint8_t* line;
+/// size_t line_len;
+/// if (cimpl_stream_read_until(stream, '\n', &line, &line_len) == 0) {
+///     // use line[0..line_len)
+///     cimpl_free(line);
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn cimpl_stream_read_until(
+    stream: *mut CimplBufStream,
+    delimiter: u8,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplBufStream);
+            ptr_or_return_int!(out);
+            ptr_or_return_int!(out_len);
+
+            let bytes = ok_or_return!(s.read_until(delimiter), |b| b, -1, ERROR_MAPPER);
+
+            unsafe {
+                *out_len = bytes.len();
+                *out = cimpl::to_c_bytes(bytes) as *mut u8;
+            }
+            0
+        },
+        -2
+    )
+}
+
+/// Reads a single line (up to and including `'\n'`) from a buffered stream.
+/// Equivalent to `cimpl_stream_read_until(stream, '\n', out, out_len)`.
+///
+/// # Memory Management
+/// `*out` must be freed with `cimpl_free()`.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_read_line(
+    stream: *mut CimplBufStream,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    cimpl_stream_read_until(stream, b'\n', out, out_len)
+}
+
+// ============================================================================
+// Stream Copy
+// ============================================================================
+
+/// Size of the reusable buffer used by `cimpl_stream_copy`, matching the
+/// default used by `std::io::copy`.
+const STREAM_COPY_BUFFER_SIZE: usize = 8 * 1024;
+
+/// Copies all bytes from `source` into `dest`, modeled on `std::io::copy`.
+///
+/// Uses a single reusable buffer, reading from `source` and writing the
+/// whole chunk to `dest` (looping to handle short writes) until a read
+/// returns zero bytes.
+///
+/// # Parameters
+/// - `source`: The stream to read from
+/// - `dest`: The stream to write to
+/// - `out_bytes`: Receives the total number of bytes copied, even if the
+///   copy stops early due to an error
+///
+/// # Returns
+/// - 0 on success
+/// - -1 on error, including the first failing read or write (its packed
+///   code is then available from `cimpl_stream_error_code()`)
+///
+/// # Example
+/// ```c
+/// uint64_t copied;
+/// if (cimpl_stream_copy(source, dest, &copied) != 0) {
+///     fprintf(stderr, "Copy failed after %llu bytes\n", (unsigned long long)copied);
+/// }
+/// ```
+#[no_mangle]
+pub extern "C" fn cimpl_stream_copy(
+    source: *mut CimplStream,
+    dest: *mut CimplStream,
+    out_bytes: *mut u64,
+) -> i32 {
+    call_with_result!(
+        {
+            let src = deref_mut_or_return_neg!(source, CimplStream);
+            let dst = deref_mut_or_return!(dest, CimplStream, -1);
+            ptr_or_return_int!(out_bytes);
+
+            let mut buf = vec![0u8; STREAM_COPY_BUFFER_SIZE];
+            let mut total: u64 = 0;
+
+            loop {
+                // Interrupted reads are retried rather than treated as failures,
+                // matching std::io::copy.
+                let read = loop {
+                    match src.read(&mut buf) {
+                        Ok(n) => break n,
+                        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                        Err(e) => {
+                            unsafe { *out_bytes = total };
+                            Error::from_mapper(e, ERROR_MAPPER).set_last();
+                            return -1;
+                        }
+                    }
+                };
+                if read == 0 {
+                    break;
+                }
+
+                // write_all already loops over partial writes (and retries
+                // Interrupted) on our behalf.
+                if let Err(e) = dst.write_all(&buf[..read]) {
+                    unsafe { *out_bytes = total };
+                    Error::from_mapper(e, ERROR_MAPPER).set_last();
+                    return -1;
+                }
+                total += read as u64;
+            }
+
+            unsafe { *out_bytes = total };
+            0
+        },
+        -2
+    )
+}
+
+// ============================================================================
+// In-Memory Cursor Stream
+// ============================================================================
+
+/// Backing storage for `cimpl_cursor_new`/`cimpl_cursor_from_bytes`: a
+/// growable in-memory buffer with a read/write position, like std's
+/// `Cursor<Vec<u8>>`. Used as the `CimplStreamContext` of an ordinary
+/// `CimplStream`, so it works with every existing `cimpl_stream_*` function
+/// without the caller writing any callbacks.
+struct CursorBuffer {
+    data: Vec<u8>,
+    pos: usize,
+}
+
+impl CursorBuffer {
+    unsafe extern "C" fn read_callback(
+        ctx: *mut CimplStreamContext,
+        data: *mut u8,
+        len: usize,
+    ) -> isize {
+        let cursor = &mut *(ctx as *mut CursorBuffer);
+        let available = cursor.data.len().saturating_sub(cursor.pos);
+        let to_read = available.min(len);
+
+        if to_read > 0 {
+            let slice = std::slice::from_raw_parts_mut(data, to_read);
+            slice.copy_from_slice(&cursor.data[cursor.pos..cursor.pos + to_read]);
+            cursor.pos += to_read;
+        }
+
+        to_read as isize
+    }
+
+    unsafe extern "C" fn seek_callback(
+        ctx: *mut CimplStreamContext,
+        offset: i64,
+        mode: CimplSeekMode,
+    ) -> i64 {
+        let cursor = &mut *(ctx as *mut CursorBuffer);
+        let base = match mode {
+            CimplSeekMode::Start => 0i64,
+            CimplSeekMode::Current => cursor.pos as i64,
+            CimplSeekMode::End => cursor.data.len() as i64,
+        };
+
+        let new_pos = (base + offset).clamp(0, cursor.data.len() as i64) as usize;
+        cursor.pos = new_pos;
+        new_pos as i64
+    }
+
+    unsafe extern "C" fn write_callback(
+        ctx: *mut CimplStreamContext,
+        data: *const u8,
+        len: usize,
+    ) -> isize {
+        let cursor = &mut *(ctx as *mut CursorBuffer);
+        let slice = std::slice::from_raw_parts(data, len);
+
+        if cursor.pos + len > cursor.data.len() {
+            cursor.data.resize(cursor.pos + len, 0);
+        }
+        cursor.data[cursor.pos..cursor.pos + len].copy_from_slice(slice);
+        cursor.pos += len;
+
+        len as isize
+    }
+
+    unsafe extern "C" fn flush_callback(_ctx: *mut CimplStreamContext) -> i32 {
+        0 // Nothing to flush for an in-memory buffer
+    }
+}
+
+fn cursor_stream(data: Vec<u8>) -> *mut CimplStream {
+    let context = Box::into_raw(Box::new(CursorBuffer { data, pos: 0 })) as *mut CimplStreamContext;
+
+    let stream = CimplStream {
+        context,
+        reader: CursorBuffer::read_callback,
+        seeker: CursorBuffer::seek_callback,
+        writer: Some(CursorBuffer::write_callback),
+        flusher: Some(CursorBuffer::flush_callback),
+        closer: None,
+        read_vectored: None,
+        write_vectored: None,
+        state: Cell::new(StreamState::Good),
+        seekable: Cell::new(true),
+    };
+
+    box_tracked!(stream)
+}
+
+/// Creates an empty in-memory stream backed by a growable buffer.
+///
+/// # Returns
+/// Pointer to the new stream, usable with the `cimpl_stream_*` functions.
+/// Must be freed with `cimpl_free()`.
+#[no_mangle]
+pub extern "C" fn cimpl_cursor_new() -> *mut CimplStream {
+    call_with_result!(cursor_stream(Vec::new()), std::ptr::null_mut())
+}
+
+/// Creates an in-memory stream pre-populated with a copy of `data`.
+///
+/// # Parameters
+/// - `data`: Bytes to copy into the cursor's buffer
+/// - `len`: Number of bytes to copy
+///
+/// # Returns
+/// - Pointer to the new stream on success
+/// - NULL if `data` is NULL
+#[no_mangle]
+pub extern "C" fn cimpl_cursor_from_bytes(data: *const u8, len: usize) -> *mut CimplStream {
+    call_with_result!(
+        {
+            ptr_or_return_null!(data);
+            let bytes = unsafe { std::slice::from_raw_parts(data, len) }.to_vec();
+            cursor_stream(bytes)
+        },
+        std::ptr::null_mut()
+    )
+}
+
+/// Hands back ownership of a cursor stream's accumulated buffer, consuming
+/// the stream in the process.
+///
+/// # Parameters
+/// - `stream`: A stream created by `cimpl_cursor_new`/`cimpl_cursor_from_bytes`
+/// - `out`: Receives the buffer's contents
+/// - `out_len`: Receives the buffer's length
+///
+/// # Returns
+/// - 0 on success
+/// - -1 on error
+///
+/// # Memory Management
+/// `*out` must be freed with `cimpl_free()`. `stream` is freed by this call
+/// and must not be used (or freed again) afterward.
+///
+/// # Safety
+/// `stream` must have been created by `cimpl_cursor_new` or
+/// `cimpl_cursor_from_bytes` - calling this on a stream backed by C
+/// callbacks reinterprets its context as a `CursorBuffer` and is undefined
+/// behavior.
+#[no_mangle]
+pub extern "C" fn cimpl_cursor_into_bytes(
+    stream: *mut CimplStream,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    call_with_result!(
+        {
+            let s = deref_mut_or_return_neg!(stream, CimplStream);
+            ptr_or_return_int!(out);
+            ptr_or_return_int!(out_len);
+
+            let cursor = unsafe { Box::from_raw(s.context as *mut CursorBuffer) };
+
+            unsafe {
+                *out_len = cursor.data.len();
+                *out = cimpl::to_c_bytes(cursor.data) as *mut u8;
+            }
+
+            // The context has already been reclaimed above; just drop the outer
+            // CimplStream wrapper.
+            unsafe { cimpl::cimpl_free(stream as *mut std::ffi::c_void) };
+
+            0
+        },
+        -2
+    )
 }
 
 // ============================================================================
@@ -502,6 +1966,51 @@ pub extern "C" fn cimpl_stream_error_code() -> i32 {
     Error::last_code() as i32
 }
 
+/// Gets the domain of the last error, as a `CimplErrorDomain` value.
+///
+/// # Returns
+/// - `CimplErrorDomain::None` (0) if there is no error, or the last error was
+///   not raised with structured detail (e.g. a bare panic)
+/// - The domain recorded when the error was raised, otherwise
+#[no_mangle]
+pub extern "C" fn cimpl_stream_error_domain() -> i32 {
+    STREAM_ERROR_DETAIL.with(|d| d.borrow().as_ref().map_or(CimplErrorDomain::None as i32, |d| d.domain as i32))
+}
+
+/// Gets the debug detail string for the last error, if any was recorded.
+///
+/// This is a free-form, implementation-specific string (e.g. a `{:?}`-
+/// formatted `std::io::Error`) meant for logs, distinct from the
+/// user-facing message returned by `cimpl_stream_last_error()`.
+///
+/// # Returns
+/// - Pointer to a C string, or NULL if no debug detail is available
+///
+/// # Memory Management
+/// The returned string must be freed with `cimpl_free()`.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_error_debug() -> *mut std::os::raw::c_char {
+    STREAM_ERROR_DETAIL.with(|d| match d.borrow().as_ref().and_then(|d| d.debug.clone()) {
+        Some(debug) => cimpl::to_c_string(debug),
+        None => std::ptr::null_mut(),
+    })
+}
+
+/// Gets the source location that raised the last error, if known.
+///
+/// # Returns
+/// - Pointer to a C string formatted as `"<module>@<file>:<line>"`, or NULL
+///   if the last error carries no structured location
+///
+/// # Memory Management
+/// The returned string must be freed with `cimpl_free()`.
+#[no_mangle]
+pub extern "C" fn cimpl_stream_error_location() -> *mut std::os::raw::c_char {
+    STREAM_ERROR_DETAIL
+        .with(|d| d.borrow().as_ref().map(|d| format!("{}@{}:{}", d.module, d.file, d.line)))
+        .map_or(std::ptr::null_mut(), cimpl::to_c_string)
+}
+
 /// Clears the last error.
 ///
 /// This function can be called to clear the error state before making
@@ -509,6 +2018,7 @@ pub extern "C" fn cimpl_stream_error_code() -> i32 {
 #[no_mangle]
 pub extern "C" fn cimpl_stream_clear_error() {
     Error::take_last();
+    STREAM_ERROR_DETAIL.with(|d| *d.borrow_mut() = None);
 }
 
 // ============================================================================
@@ -606,6 +2116,26 @@ mod tests {
         unsafe extern "C" fn flush_callback(_ctx: *mut CimplStreamContext) -> i32 {
             0 // Nothing to flush for memory buffer
         }
+
+        unsafe extern "C" fn write_vectored_callback(
+            ctx: *mut CimplStreamContext,
+            iovs: *const CimplIoSlice,
+            n: usize,
+        ) -> isize {
+            let iovs = std::slice::from_raw_parts(iovs, n);
+            let mut total = 0isize;
+
+            for iov in iovs {
+                let written =
+                    Self::write_callback(ctx, iov.ptr as *const u8, iov.len);
+                if written < 0 {
+                    return written;
+                }
+                total += written;
+            }
+
+            total
+        }
     }
 
     #[test]
@@ -664,10 +2194,10 @@ mod tests {
     }
 
     #[test]
-    fn test_stream_seek_operations() {
-        let buffer = Box::new(MemoryBuffer::with_data(b"0123456789".to_vec()));
+    fn test_stream_state_good_eof_bad() {
+        let buffer = Box::new(MemoryBuffer::with_data(b"hi".to_vec()));
         let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
-        
+
         let stream = cimpl_stream_new(
             ctx,
             MemoryBuffer::read_callback,
@@ -675,19 +2205,82 @@ mod tests {
             MemoryBuffer::write_callback,
             MemoryBuffer::flush_callback,
         );
-        
-        // Seek to position 5
-        let pos = cimpl_stream_seek(stream, 5, CimplSeekMode::Start);
-        assert_eq!(pos, 5);
-        
-        // Read from position 5
-        let mut buf = [0u8; 3];
-        let bytes_read = cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len());
-        assert_eq!(bytes_read, 3);
-        assert_eq!(&buf, b"567");
-        
-        // Seek backward 5 bytes from current (should be at position 3)
-        let pos = cimpl_stream_seek(stream, -5, CimplSeekMode::Current);
+
+        assert!(cimpl_stream_good(stream));
+        assert!(!cimpl_stream_eof(stream));
+        assert!(!cimpl_stream_bad(stream));
+
+        // A non-empty read that consumes all the data is still `good`.
+        let mut buf = [0u8; 2];
+        assert_eq!(cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len()), 2);
+        assert!(cimpl_stream_good(stream));
+        assert!(!cimpl_stream_eof(stream));
+
+        // The next read finds nothing left: clean EOF, not an error.
+        assert_eq!(cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len()), 0);
+        assert!(!cimpl_stream_good(stream));
+        assert!(cimpl_stream_eof(stream));
+        assert!(!cimpl_stream_bad(stream));
+
+        unsafe {
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+
+        // A real failure is sticky, even across an intervening successful call.
+        let buffer = Box::new(MemoryBuffer::with_data(b"hi".to_vec()));
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+        let stream = cimpl_stream_new(
+            ctx,
+            would_block_read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        let mut buf = [0u8; 2];
+        assert_eq!(cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len()), -1);
+        assert!(cimpl_stream_bad(stream));
+
+        assert_eq!(cimpl_stream_write(stream, b"ok".as_ptr(), 2), 2);
+        assert!(cimpl_stream_bad(stream), "bad stays sticky across an unrelated successful call");
+
+        // A successful seek is the recovery path, same as C's fseek()/rewind().
+        assert_eq!(cimpl_stream_seek(stream, 0, CimplSeekMode::Start), 0);
+        assert!(cimpl_stream_good(stream));
+        assert!(!cimpl_stream_bad(stream));
+
+        unsafe {
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_stream_seek_operations() {
+        let buffer = Box::new(MemoryBuffer::with_data(b"0123456789".to_vec()));
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+        
+        let stream = cimpl_stream_new(
+            ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+        
+        // Seek to position 5
+        let pos = cimpl_stream_seek(stream, 5, CimplSeekMode::Start);
+        assert_eq!(pos, 5);
+        
+        // Read from position 5
+        let mut buf = [0u8; 3];
+        let bytes_read = cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len());
+        assert_eq!(bytes_read, 3);
+        assert_eq!(&buf, b"567");
+        
+        // Seek backward 5 bytes from current (should be at position 3)
+        let pos = cimpl_stream_seek(stream, -5, CimplSeekMode::Current);
         assert_eq!(pos, 3);
         
         // Read from position 3
@@ -710,6 +2303,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stream_tell_and_seekable() {
+        let buffer = Box::new(MemoryBuffer::with_data(b"0123456789".to_vec()));
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new(
+            ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        assert!(cimpl_stream_is_seekable(stream));
+
+        assert_eq!(cimpl_stream_tell(stream), 0);
+        assert_eq!(cimpl_stream_seek(stream, 4, CimplSeekMode::Start), 4);
+        assert_eq!(cimpl_stream_tell(stream), 4);
+
+        let mut buf = [0u8; 3];
+        assert_eq!(cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len()), 3);
+        assert_eq!(cimpl_stream_tell(stream), 7);
+
+        assert_eq!(cimpl_stream_set_seekable(stream, false), 0);
+        assert!(!cimpl_stream_is_seekable(stream));
+        assert_eq!(cimpl_stream_set_seekable(stream, true), 0);
+        assert!(cimpl_stream_is_seekable(stream));
+
+        unsafe {
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
     #[test]
     fn test_stream_flush() {
         let buffer = Box::new(MemoryBuffer::new());
@@ -771,6 +2398,443 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bufstream_write_read_roundtrip() {
+        let buffer = Box::new(MemoryBuffer::new());
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new(
+            ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        let buf_stream = cimpl_bufstream_new(stream, 4, 4);
+        assert!(!buf_stream.is_null());
+
+        // Small writes should accumulate in the write buffer rather than
+        // immediately reaching the inner stream.
+        assert_eq!(cimpl_bufstream_write(buf_stream, b"ab".as_ptr(), 2), 2);
+        assert_eq!(cimpl_bufstream_write(buf_stream, b"cd".as_ptr(), 2), 2);
+        assert_eq!(cimpl_bufstream_flush(buf_stream), 0);
+
+        assert_eq!(cimpl_bufstream_seek(buf_stream, 0, CimplSeekMode::Start), 0);
+
+        let mut read_buf = [0u8; 10];
+        let bytes_read = cimpl_bufstream_read(buf_stream, read_buf.as_mut_ptr(), read_buf.len());
+        assert_eq!(bytes_read, 4);
+        assert_eq!(&read_buf[..4], b"abcd");
+
+        unsafe {
+            cimpl::cimpl_free(buf_stream as *mut std::ffi::c_void);
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_bufstream_large_write_bypasses_buffer() {
+        let buffer = Box::new(MemoryBuffer::new());
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new(
+            ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        let buf_stream = cimpl_bufstream_new(stream, 4, 4);
+
+        // A write larger than the write buffer's capacity should go straight
+        // through to the inner stream without needing an explicit flush.
+        let data = b"this is longer than four bytes";
+        let bytes_written = cimpl_bufstream_write(buf_stream, data.as_ptr(), data.len());
+        assert_eq!(bytes_written, data.len() as isize);
+
+        assert_eq!(cimpl_bufstream_seek(buf_stream, 0, CimplSeekMode::Start), 0);
+
+        let mut read_buf = [0u8; 64];
+        let bytes_read = cimpl_bufstream_read(buf_stream, read_buf.as_mut_ptr(), read_buf.len());
+        assert_eq!(bytes_read as usize, data.len());
+        assert_eq!(&read_buf[..data.len()], data);
+
+        unsafe {
+            cimpl::cimpl_free(buf_stream as *mut std::ffi::c_void);
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_read_line() {
+        let buffer = Box::new(MemoryBuffer::with_data(b"first\nsecond\nthird".to_vec()));
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new(
+            ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+        // Small buffer to exercise refilling across multiple lines.
+        let buf_stream = cimpl_bufstream_new(stream, 4, 4);
+
+        let mut out: *mut u8 = std::ptr::null_mut();
+        let mut out_len: usize = 0;
+
+        assert_eq!(cimpl_stream_read_line(buf_stream, &mut out, &mut out_len), 0);
+        let line = unsafe { std::slice::from_raw_parts(out, out_len) };
+        assert_eq!(line, b"first\n");
+        unsafe { cimpl::cimpl_free(out as *mut std::ffi::c_void) };
+
+        assert_eq!(cimpl_stream_read_line(buf_stream, &mut out, &mut out_len), 0);
+        let line = unsafe { std::slice::from_raw_parts(out, out_len) };
+        assert_eq!(line, b"second\n");
+        unsafe { cimpl::cimpl_free(out as *mut std::ffi::c_void) };
+
+        // Final chunk has no trailing delimiter: a partial line before EOF.
+        assert_eq!(cimpl_stream_read_line(buf_stream, &mut out, &mut out_len), 0);
+        let line = unsafe { std::slice::from_raw_parts(out, out_len) };
+        assert_eq!(line, b"third");
+        unsafe { cimpl::cimpl_free(out as *mut std::ffi::c_void) };
+
+        // Clean EOF: nothing left to read.
+        assert_eq!(cimpl_stream_read_line(buf_stream, &mut out, &mut out_len), 0);
+        assert_eq!(out_len, 0);
+        unsafe { cimpl::cimpl_free(out as *mut std::ffi::c_void) };
+
+        unsafe {
+            cimpl::cimpl_free(buf_stream as *mut std::ffi::c_void);
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_stream_copy() {
+        let source_buffer = Box::new(MemoryBuffer::with_data(b"copy this data".to_vec()));
+        let source_ctx = Box::into_raw(source_buffer) as *mut CimplStreamContext;
+        let source = cimpl_stream_new(
+            source_ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        let dest_buffer = Box::new(MemoryBuffer::new());
+        let dest_ctx = Box::into_raw(dest_buffer) as *mut CimplStreamContext;
+        let dest = cimpl_stream_new(
+            dest_ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        let mut copied: u64 = 0;
+        assert_eq!(cimpl_stream_copy(source, dest, &mut copied), 0);
+        assert_eq!(copied, 14);
+
+        cimpl_stream_seek(dest, 0, CimplSeekMode::Start);
+        let mut read_buf = [0u8; 32];
+        let bytes_read = cimpl_stream_read(dest, read_buf.as_mut_ptr(), read_buf.len());
+        assert_eq!(bytes_read as u64, copied);
+        assert_eq!(&read_buf[..copied as usize], b"copy this data");
+
+        unsafe {
+            cimpl::cimpl_free(source as *mut std::ffi::c_void);
+            cimpl::cimpl_free(dest as *mut std::ffi::c_void);
+            let _ = Box::from_raw(source_ctx as *mut MemoryBuffer);
+            let _ = Box::from_raw(dest_ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_cursor_write_read_and_into_bytes() {
+        let cursor = cimpl_cursor_new();
+        assert!(!cursor.is_null());
+
+        let data = b"cursor data";
+        assert_eq!(
+            cimpl_stream_write(cursor, data.as_ptr(), data.len()),
+            data.len() as isize
+        );
+
+        cimpl_stream_seek(cursor, 0, CimplSeekMode::Start);
+        let mut buf = [0u8; 32];
+        let bytes_read = cimpl_stream_read(cursor, buf.as_mut_ptr(), buf.len());
+        assert_eq!(bytes_read as usize, data.len());
+        assert_eq!(&buf[..data.len()], data);
+
+        let mut out: *mut u8 = std::ptr::null_mut();
+        let mut out_len: usize = 0;
+        assert_eq!(cimpl_cursor_into_bytes(cursor, &mut out, &mut out_len), 0);
+        assert_eq!(out_len, data.len());
+        let bytes = unsafe { std::slice::from_raw_parts(out, out_len) };
+        assert_eq!(bytes, data);
+
+        unsafe { cimpl::cimpl_free(out as *mut std::ffi::c_void) };
+    }
+
+    #[test]
+    fn test_cursor_from_bytes() {
+        let initial = b"0123456789";
+        let cursor = cimpl_cursor_from_bytes(initial.as_ptr(), initial.len());
+        assert!(!cursor.is_null());
+
+        let pos = cimpl_stream_seek(cursor, -3, CimplSeekMode::End);
+        assert_eq!(pos, 7);
+
+        let mut buf = [0u8; 3];
+        let bytes_read = cimpl_stream_read(cursor, buf.as_mut_ptr(), buf.len());
+        assert_eq!(bytes_read, 3);
+        assert_eq!(&buf, b"789");
+
+        unsafe { cimpl::cimpl_free(cursor as *mut std::ffi::c_void) };
+    }
+
+    /// A context-free callback that always fails, reporting `WouldBlock`.
+    unsafe extern "C" fn would_block_read_callback(
+        _ctx: *mut CimplStreamContext,
+        _data: *mut u8,
+        _len: usize,
+    ) -> isize {
+        cimpl_stream_set_callback_error(CIMPL_IO_KIND_WOULD_BLOCK, -1);
+        -1
+    }
+
+    #[test]
+    fn test_callback_error_preserves_would_block() {
+        let buffer = Box::new(MemoryBuffer::new());
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new(
+            ctx,
+            would_block_read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        let mut buf = [0u8; 8];
+        assert_eq!(cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len()), -1);
+        assert_eq!(
+            cimpl_stream_error_code(),
+            CIMPL_IO_KIND_WOULD_BLOCK << 1
+        );
+
+        unsafe {
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_error_msg_macro_populates_all_fields() {
+        cimpl_stream_clear_error();
+
+        error_msg!(CimplErrorDomain::Unsupported, 42, "unsupported thing".to_string(), Some("detail".to_string()));
+
+        assert_eq!(cimpl_stream_error_domain(), CimplErrorDomain::Unsupported as i32);
+        assert_eq!(cimpl_stream_error_code(), 42);
+
+        let debug = cimpl_stream_error_debug();
+        assert!(!debug.is_null());
+        let debug_str = unsafe { std::ffi::CStr::from_ptr(debug) }.to_string_lossy().into_owned();
+        assert_eq!(debug_str, "detail");
+        unsafe { cimpl::cimpl_free(debug as *mut std::ffi::c_void) };
+
+        let location = cimpl_stream_error_location();
+        assert!(!location.is_null());
+        let location_str = unsafe { std::ffi::CStr::from_ptr(location) }.to_string_lossy().into_owned();
+        assert!(location_str.contains("lib.rs"));
+        unsafe { cimpl::cimpl_free(location as *mut std::ffi::c_void) };
+
+        cimpl_stream_clear_error();
+    }
+
+    #[test]
+    fn test_structured_error_detail_for_io_failure() {
+        let buffer = Box::new(MemoryBuffer::new());
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new(
+            ctx,
+            would_block_read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        cimpl_stream_clear_error();
+        assert_eq!(cimpl_stream_error_domain(), CimplErrorDomain::None as i32);
+        assert!(cimpl_stream_error_debug().is_null());
+        assert!(cimpl_stream_error_location().is_null());
+
+        let mut buf = [0u8; 8];
+        assert_eq!(cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len()), -1);
+        assert_eq!(cimpl_stream_error_domain(), CimplErrorDomain::Io as i32);
+
+        let debug = cimpl_stream_error_debug();
+        assert!(!debug.is_null());
+        unsafe { cimpl::cimpl_free(debug as *mut std::ffi::c_void) };
+
+        let location = cimpl_stream_error_location();
+        assert!(!location.is_null());
+        let location_str = unsafe { std::ffi::CStr::from_ptr(location) }.to_string_lossy().into_owned();
+        assert!(location_str.contains("lib.rs"));
+        unsafe { cimpl::cimpl_free(location as *mut std::ffi::c_void) };
+
+        cimpl_stream_clear_error();
+        assert_eq!(cimpl_stream_error_domain(), CimplErrorDomain::None as i32);
+        assert!(cimpl_stream_error_debug().is_null());
+
+        unsafe {
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_read_only_stream_rejects_writes_with_unsupported_domain() {
+        let buffer = Box::new(MemoryBuffer::with_data(b"hi".to_vec()));
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new_with_close(
+            ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            None,
+            None,
+            None,
+        );
+        assert!(!stream.is_null());
+
+        cimpl_stream_clear_error();
+        assert_eq!(cimpl_stream_write(stream, b"no".as_ptr(), 2), -1);
+        assert_eq!(cimpl_stream_error_domain(), CimplErrorDomain::Unsupported as i32);
+
+        // Flushing a writer-less stream is a no-op, not an error.
+        assert_eq!(cimpl_stream_flush(stream), 0);
+
+        let mut buf = [0u8; 2];
+        assert_eq!(cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len()), 2);
+        assert_eq!(&buf, b"hi");
+
+        unsafe {
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    struct CloseTrackingContext {
+        closed: std::sync::atomic::AtomicUsize,
+    }
+
+    unsafe extern "C" fn tracking_read_callback(
+        _ctx: *mut CimplStreamContext,
+        _data: *mut u8,
+        _len: usize,
+    ) -> isize {
+        0
+    }
+
+    unsafe extern "C" fn tracking_seek_callback(
+        _ctx: *mut CimplStreamContext,
+        _offset: i64,
+        _mode: CimplSeekMode,
+    ) -> i64 {
+        0
+    }
+
+    unsafe extern "C" fn tracking_close_callback(ctx: *mut CimplStreamContext) {
+        let ctx = &*(ctx as *const CloseTrackingContext);
+        ctx.closed.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_close_callback_runs_exactly_once_on_free() {
+        let tracker = Box::new(CloseTrackingContext { closed: std::sync::atomic::AtomicUsize::new(0) });
+        let ctx = Box::into_raw(tracker) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new_with_close(
+            ctx,
+            tracking_read_callback,
+            tracking_seek_callback,
+            None,
+            None,
+            Some(tracking_close_callback),
+        );
+        assert!(!stream.is_null());
+
+        let tracker = unsafe { &*(ctx as *const CloseTrackingContext) };
+        assert_eq!(tracker.closed.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        unsafe { cimpl::cimpl_free(stream as *mut std::ffi::c_void) };
+        assert_eq!(tracker.closed.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        unsafe { drop(Box::from_raw(ctx as *mut CloseTrackingContext)) };
+    }
+
+    #[test]
+    fn test_stream_from_uri_rejects_unsupported_scheme() {
+        let uri = std::ffi::CString::new("ftp://example.com/file").unwrap();
+
+        assert!(!cimpl_stream_uri_is_supported(uri.as_ptr()));
+
+        cimpl_stream_clear_error();
+        let stream = cimpl_stream_from_uri(uri.as_ptr());
+        assert!(stream.is_null());
+        assert_eq!(cimpl_stream_error_domain(), CimplErrorDomain::UnsupportedProtocol as i32);
+    }
+
+    #[test]
+    fn test_stream_from_uri_reads_local_file() {
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "cimpl_stream_from_uri_test_{:?}_{}",
+            std::thread::current().id(),
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
+        ));
+        std::fs::write(&path, b"hello from disk").unwrap();
+
+        let uri = std::ffi::CString::new(format!("file://{}", path.display())).unwrap();
+        assert!(cimpl_stream_uri_is_supported(uri.as_ptr()));
+
+        let stream = cimpl_stream_from_uri(uri.as_ptr());
+        assert!(!stream.is_null());
+
+        let mut buf = [0u8; 15];
+        assert_eq!(cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len()), 15);
+        assert_eq!(&buf, b"hello from disk");
+
+        unsafe { cimpl::cimpl_free(stream as *mut std::ffi::c_void) };
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_stream_from_uri_missing_file_is_io_error() {
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "cimpl_stream_from_uri_missing_{:?}_{}",
+            std::thread::current().id(),
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let uri = std::ffi::CString::new(format!("file://{}", path.display())).unwrap();
+        cimpl_stream_clear_error();
+        let stream = cimpl_stream_from_uri(uri.as_ptr());
+        assert!(stream.is_null());
+        assert_eq!(cimpl_stream_error_domain(), CimplErrorDomain::Io as i32);
+    }
+
     #[test]
     fn test_error_messages() {
         // Clear any previous error
@@ -795,5 +2859,122 @@ mod tests {
         cimpl_stream_clear_error();
         assert_eq!(cimpl_stream_error_code(), 0);
     }
+
+    #[test]
+    fn test_write_vectored_uses_callback_when_present() {
+        let buffer = Box::new(MemoryBuffer::new());
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new(
+            ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        assert!(!cimpl_stream_is_write_vectored(stream));
+
+        assert_eq!(
+            cimpl_stream_set_vectored_callbacks(
+                stream,
+                None,
+                Some(MemoryBuffer::write_vectored_callback),
+            ),
+            0
+        );
+        assert!(cimpl_stream_is_write_vectored(stream));
+
+        let first = b"Hello, ";
+        let second = b"World!";
+        let iovs = [
+            CimplIoSlice {
+                ptr: first.as_ptr() as *mut u8,
+                len: first.len(),
+            },
+            CimplIoSlice {
+                ptr: second.as_ptr() as *mut u8,
+                len: second.len(),
+            },
+        ];
+
+        let written = cimpl_stream_write_vectored(stream, iovs.as_ptr(), iovs.len());
+        assert_eq!(written as usize, first.len() + second.len());
+
+        cimpl_stream_seek(stream, 0, CimplSeekMode::Start);
+        let mut buf = [0u8; 13];
+        let read = cimpl_stream_read(stream, buf.as_mut_ptr(), buf.len());
+        assert_eq!(read as usize, buf.len());
+        assert_eq!(&buf, b"Hello, World!");
+
+        unsafe {
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_read_vectored_falls_back_to_scalar_reader_when_unset() {
+        let buffer = Box::new(MemoryBuffer::with_data(b"abcdef".to_vec()));
+        let ctx = Box::into_raw(buffer) as *mut CimplStreamContext;
+
+        let stream = cimpl_stream_new(
+            ctx,
+            MemoryBuffer::read_callback,
+            MemoryBuffer::seek_callback,
+            MemoryBuffer::write_callback,
+            MemoryBuffer::flush_callback,
+        );
+
+        assert!(!cimpl_stream_is_read_vectored(stream));
+
+        let mut first = [0u8; 3];
+        let mut second = [0u8; 3];
+        let iovs = [
+            CimplIoSlice {
+                ptr: first.as_mut_ptr(),
+                len: first.len(),
+            },
+            CimplIoSlice {
+                ptr: second.as_mut_ptr(),
+                len: second.len(),
+            },
+        ];
+
+        // With no vectored callback attached, the fallback only fills the
+        // first segment, matching the scalar reader's contract.
+        let read = cimpl_stream_read_vectored(stream, iovs.as_ptr() as *mut CimplIoSlice, iovs.len());
+        assert_eq!(read, 3);
+        assert_eq!(&first, b"abc");
+
+        unsafe {
+            cimpl::cimpl_free(stream as *mut std::ffi::c_void);
+            let _ = Box::from_raw(ctx as *mut MemoryBuffer);
+        }
+    }
+
+    #[test]
+    fn test_call_with_result_catches_panic() {
+        // The stream operations (cimpl_stream_read, _write, _seek, ...) wrap
+        // their bodies in this same macro.
+        // Exercise it directly with a synthetic panic, rather than through a
+        // callback - a panic inside a caller-supplied `extern "C"` callback
+        // aborts the process at that function's own ABI boundary before it
+        // could ever reach a `catch_unwind` further up the call stack.
+        cimpl_stream_clear_error();
+
+        let result: isize = call_with_result!(panic!("synthetic panic for testing"), -2);
+        assert_eq!(result, -2);
+
+        assert_eq!(cimpl_stream_error_code(), cimpl::error::ErrorCode::Panic as i32);
+
+        let error_msg = cimpl_stream_last_error();
+        assert!(!error_msg.is_null());
+        let message = unsafe { std::ffi::CStr::from_ptr(error_msg) }
+            .to_string_lossy()
+            .into_owned();
+        assert!(message.contains("synthetic panic for testing"));
+        unsafe { cimpl::cimpl_free(error_msg as *mut std::ffi::c_void) };
+    }
 }
 
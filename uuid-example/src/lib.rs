@@ -8,13 +8,16 @@
 //! - Multiple constructors and methods
 //! - Error handling with error codes
 //! - Clean macro usage throughout
+//! - An opt-in handle-based API (`uuid_handle_*`) as an alternative to the
+//!   pointer-based one, backed by `cimpl::HandleMap`
 
 use std::os::raw::c_char;
 use std::str::FromStr;
 
 use cimpl::{
-    box_tracked, cstr_or_return_null, deref_or_return_false, deref_or_return_null,
-    deref_or_return_zero, ok_or_return_null, option_to_c_string, to_c_bytes, to_c_string, Error,
+    box_tracked, cstr_or_return, cstr_or_return_null, deref_or_return_false, deref_or_return_null,
+    deref_or_return_zero, ok_or_return, ok_or_return_null, option_to_c_string, to_c_bytes,
+    to_c_string, Error, HandleMap,
 };
 
 // Use uuid::Uuid directly - it's already opaque to C!
@@ -161,6 +164,66 @@ pub extern "C" fn uuid_equals(a: *mut Uuid, b: *mut Uuid) -> bool {
     uuid_a == uuid_b
 }
 
+// ============================================================================
+// Handle-Based API (opt-in alternative to raw pointers)
+// ============================================================================
+//
+// Everything above hands out a `*mut Uuid` validated by `box_tracked!`'s
+// address-based registry. The functions below offer the same operations
+// through `cimpl::HandleMap` instead: an opaque `u64` handle that can't
+// collide with a freshly allocated `Uuid` at a reused address, and that
+// `uuid_handle_free` permanently invalidates for every outstanding copy.
+// Pick whichever fits the host binding better - the two handle kinds are
+// not interchangeable.
+
+/// The process-wide table backing the handle-based UUID API.
+fn uuid_handles() -> &'static HandleMap<Uuid> {
+    static HANDLES: std::sync::OnceLock<HandleMap<Uuid>> = std::sync::OnceLock::new();
+    HANDLES.get_or_init(HandleMap::new)
+}
+
+/// Creates a new random UUID (version 4) and returns a handle to it.
+#[no_mangle]
+pub extern "C" fn uuid_handle_new_v4() -> u64 {
+    uuid_handles().insert(Uuid::new_v4())
+}
+
+/// Parses a UUID from a string and returns a handle to it, or `0` on error.
+#[no_mangle]
+pub extern "C" fn uuid_handle_parse(s: *const c_char) -> u64 {
+    let s_str = cstr_or_return!(s, 0);
+    let uuid = ok_or_return!(Uuid::from_str(&s_str), |v| v, 0, UUID_ERROR_MAPPER);
+    uuid_handles().insert(uuid)
+}
+
+/// Converts a handle's UUID to its string representation.
+#[no_mangle]
+pub extern "C" fn uuid_handle_to_string(handle: u64) -> *mut c_char {
+    cimpl::deref_handle_or_return_null!(uuid_handles(), handle, |uuid| to_c_string(
+        uuid.to_string()
+    ))
+}
+
+/// Checks if a handle's UUID is nil (all zeros).
+#[no_mangle]
+pub extern "C" fn uuid_handle_is_nil(handle: u64) -> bool {
+    cimpl::deref_handle_or_return_false!(uuid_handles(), handle, |uuid| uuid.is_nil())
+}
+
+/// Releases a handle, permanently invalidating every outstanding copy of it.
+///
+/// Returns `0` on success, `-1` if the handle is stale, forged, or already freed.
+#[no_mangle]
+pub extern "C" fn uuid_handle_free(handle: u64) -> i32 {
+    match uuid_handles().remove(handle) {
+        Ok(_) => 0,
+        Err(e) => {
+            e.set_last();
+            -1
+        }
+    }
+}
+
 // ============================================================================
 // Memory Management
 // ============================================================================